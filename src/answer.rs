@@ -0,0 +1,160 @@
+//! A puzzle answer: an integer of any size or a string.
+
+use num_bigint::{BigInt, BigUint};
+use std::convert::Infallible;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Built only through `From` and `FromStr`, which keep integers in the
+/// smallest representation that holds them, so the same value compares equal
+/// whatever type it was produced with.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Answer(Repr);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Repr {
+    Int(i128),
+    Big(BigInt),
+    Text(String),
+}
+
+impl Answer {
+    fn int(n: i128) -> Self {
+        Answer(Repr::Int(n))
+    }
+
+    /// The value if it is an integer that fits in an `i128`.
+    pub fn as_int(&self) -> Option<i128> {
+        match self.0 {
+            Repr::Int(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        !matches!(self.0, Repr::Text(_))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            Repr::Int(n) => write!(f, "{n}"),
+            Repr::Big(n) => write!(f, "{n}"),
+            Repr::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::int(i128::from(n))
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i8, i16, i32, i64, i128, u8, u16, u32, u64);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::int(n as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        Answer::int(n as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        i128::try_from(n).map_or_else(|_| Answer::from(BigInt::from(n)), Answer::int)
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        i128::try_from(&n).map_or(Answer(Repr::Big(n)), Answer::int)
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(n: BigUint) -> Self {
+        Answer::from(BigInt::from(n))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer(Repr::Text(s))
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::from(s.to_string())
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    /// Integers in canonical form (no sign on zero, no leading zeros) become
+    /// numbers; anything else is kept as text so it prints back unchanged.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix('-').unwrap_or(s);
+        let canonical = !digits.is_empty()
+            && digits.bytes().all(|b| b.is_ascii_digit())
+            && (digits == "0" || !digits.starts_with('0'))
+            && s != "-0";
+        if canonical {
+            Ok(Answer::from(s.parse::<BigInt>().unwrap()))
+        } else {
+            Ok(Answer::from(s.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_compare_across_widths() {
+        assert_eq!(Answer::from(42usize), Answer::from(42u64));
+        assert_eq!(Answer::from(42u8), Answer::from(BigInt::from(42)));
+        assert_eq!(Answer::from(-7i32), Answer::from(-7i128));
+        assert_ne!(Answer::from(42u64), Answer::from("42"));
+    }
+
+    #[test]
+    fn big_values() {
+        let big = Answer::from(u128::MAX);
+        assert!(big.is_numeric());
+        assert_eq!(big.as_int(), None);
+        assert_eq!(big.to_string(), u128::MAX.to_string());
+        assert_eq!(big, u128::MAX.to_string().parse().unwrap());
+        let huge = "123456789012345678901234567890123456789012345678901234567890";
+        assert_eq!(huge.parse::<Answer>().unwrap().to_string(), huge);
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!("1092".parse::<Answer>().unwrap(), Answer::from(1092));
+        assert_eq!("-5".parse::<Answer>().unwrap(), Answer::from(-5));
+        assert_eq!("0".parse::<Answer>().unwrap(), Answer::from(0));
+        for text in ["EFEYKFRFIJ", "007", "-0", "", "1,2,3"] {
+            assert_eq!(
+                text.parse::<Answer>().unwrap(),
+                Answer::from(text),
+                "{text}"
+            );
+        }
+    }
+}
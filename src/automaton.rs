@@ -0,0 +1,221 @@
+//! Cellular automata on a [`Grid`], run until no cell changes.
+//!
+//! Only cells next to the previous round's changes are evaluated again, so a run
+//! costs about as much as the number of changes rather than rounds × grid size.
+
+use crate::direction::{Direction, Direction8};
+use crate::grid::Grid;
+
+/// Which cells a rule gets to see.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// The four orthogonal neighbours.
+    VonNeumann,
+    /// The eight orthogonal and diagonal neighbours.
+    Moore,
+}
+
+impl Neighborhood {
+    fn offsets(self) -> Vec<(isize, isize)> {
+        match self {
+            Neighborhood::VonNeumann => Direction::ALL.iter().map(|d| d.offset()).collect(),
+            Neighborhood::Moore => Direction8::ALL.iter().map(|d| d.offset()).collect(),
+        }
+    }
+}
+
+/// When a round's changes become visible to other cells.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Update {
+    /// Every cell in a round sees the grid as it was when the round started.
+    #[default]
+    Synchronous,
+    /// Cells are updated in row-major order, each seeing the changes made before it.
+    Asynchronous,
+}
+
+pub struct Automaton<F> {
+    offsets: Vec<(isize, isize)>,
+    update: Update,
+    max_rounds: usize,
+    rule: F,
+}
+
+/// The outcome of [`Automaton::run`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Run<T> {
+    /// The grid once it stopped changing, or after the round limit.
+    pub grid: Grid<T>,
+    /// The cells changed in each round, in the order they were updated.
+    pub changes: Vec<Vec<(usize, usize)>>,
+}
+
+impl<T> Run<T> {
+    /// Rounds in which something changed.
+    pub fn rounds(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn total_changes(&self) -> usize {
+        self.changes.iter().map(Vec::len).sum()
+    }
+
+    /// The grid with each cell's last changing round (1-based), or `None` if it never changed.
+    pub fn last_changed(&self) -> Grid<Option<usize>> {
+        let mut rounds = Grid::new(self.grid.width(), self.grid.height(), None);
+        for (round, cells) in self.changes.iter().enumerate() {
+            for &p in cells {
+                rounds[p] = Some(round + 1);
+            }
+        }
+        rounds
+    }
+}
+
+impl<F> Automaton<F> {
+    /// An automaton applying `rule` to each cell and its neighbours. The rule
+    /// returns the cell's new value, or `None` to leave it alone.
+    pub fn new<T>(neighborhood: Neighborhood, rule: F) -> Self
+    where
+        F: FnMut(&T, &[&T]) -> Option<T>,
+    {
+        Self {
+            offsets: neighborhood.offsets(),
+            update: Update::default(),
+            max_rounds: usize::MAX,
+            rule,
+        }
+    }
+
+    pub fn update(mut self, update: Update) -> Self {
+        self.update = update;
+        self
+    }
+
+    /// Stops after `rounds` rounds even if the grid is still changing.
+    pub fn max_rounds(mut self, rounds: usize) -> Self {
+        self.max_rounds = rounds;
+        self
+    }
+
+    /// Applies the rule until a round changes nothing.
+    pub fn run<T>(&mut self, mut grid: Grid<T>) -> Run<T>
+    where
+        T: PartialEq,
+        F: FnMut(&T, &[&T]) -> Option<T>,
+    {
+        let mut changes = Vec::new();
+        let mut frontier: Vec<(usize, usize)> = grid.positions().collect();
+        let mut queued = Grid::new(grid.width(), grid.height(), false);
+        while !frontier.is_empty() && changes.len() < self.max_rounds {
+            let changed = match self.update {
+                Update::Synchronous => {
+                    let updates: Vec<_> = frontier
+                        .iter()
+                        .filter_map(|&p| self.evaluate(&grid, p).map(|v| (p, v)))
+                        .collect();
+                    updates
+                        .into_iter()
+                        .map(|(p, v)| {
+                            grid[p] = v;
+                            p
+                        })
+                        .collect()
+                }
+                Update::Asynchronous => {
+                    let mut changed = Vec::new();
+                    for &p in &frontier {
+                        if let Some(v) = self.evaluate(&grid, p) {
+                            grid[p] = v;
+                            changed.push(p);
+                        }
+                    }
+                    changed
+                }
+            };
+            if changed.is_empty() {
+                break;
+            }
+
+            frontier.clear();
+            for &(x, y) in &changed {
+                let around = self
+                    .offsets
+                    .iter()
+                    .filter_map(|&(dx, dy)| grid.offset(x, y, dx, dy));
+                for p in std::iter::once((x, y)).chain(around) {
+                    if !queued[p] {
+                        queued[p] = true;
+                        frontier.push(p);
+                    }
+                }
+            }
+            for &p in &frontier {
+                queued[p] = false;
+            }
+            frontier.sort_unstable_by_key(|&(x, y)| (y, x));
+            changes.push(changed);
+        }
+        Run { grid, changes }
+    }
+
+    /// The rule's new value for `(x, y)`, if it differs from the current one.
+    fn evaluate<T>(&mut self, grid: &Grid<T>, (x, y): (usize, usize)) -> Option<T>
+    where
+        T: PartialEq,
+        F: FnMut(&T, &[&T]) -> Option<T>,
+    {
+        let neighbors: Vec<&T> = self
+            .offsets
+            .iter()
+            .filter_map(|&(dx, dy)| grid.offset(x, y, dx, dy))
+            .map(|p| &grid[p])
+            .collect();
+        let cell = &grid[(x, y)];
+        (self.rule)(cell, &neighbors).filter(|v| v != cell)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(cell: &bool, neighbors: &[&bool]) -> Option<bool> {
+        let alive = neighbors.iter().filter(|&&&n| n).count();
+        Some(alive == 3 || (*cell && alive == 2))
+    }
+
+    #[test]
+    fn blinker_oscillates_until_the_limit() {
+        let grid =
+            Grid::parse_with(".....\n..#..\n..#..\n..#..\n.....\n", |c| Ok(c == '#')).unwrap();
+        let run = Automaton::new(Neighborhood::Moore, life)
+            .max_rounds(3)
+            .run(grid.clone());
+        assert_eq!(run.rounds(), 3);
+        assert_eq!(run.total_changes(), 12);
+        assert_eq!(run.grid, grid.transpose());
+    }
+
+    #[test]
+    fn synchronous_and_asynchronous_spread() {
+        // Fill every cell orthogonally next to a filled one.
+        let spread = |cell: &bool, neighbors: &[&bool]| {
+            (!cell && neighbors.iter().any(|&&n| n)).then_some(true)
+        };
+        let grid = Grid::parse_with("#....\n", |c| Ok(c == '#')).unwrap();
+
+        let run = Automaton::new(Neighborhood::VonNeumann, spread).run(grid.clone());
+        assert_eq!(run.rounds(), 4);
+        assert_eq!(run.changes[0], [(1, 0)]);
+        assert!(run.grid.cells().iter().all(|&c| c));
+        let rounds: Vec<_> = run.last_changed().cells().to_vec();
+        assert_eq!(rounds, [None, Some(1), Some(2), Some(3), Some(4)]);
+
+        let run = Automaton::new(Neighborhood::VonNeumann, spread)
+            .update(Update::Asynchronous)
+            .run(grid);
+        assert_eq!(run.rounds(), 1);
+        assert_eq!(run.changes[0], [(1, 0), (2, 0), (3, 0), (4, 0)]);
+    }
+}
@@ -1,7 +1,10 @@
 #![allow(unexpected_cfgs)]
 #![warn(clippy::all, clippy::pedantic)]
 
-use adv_code_2025::start_day;
+use adv_code_2025::differential::Differential;
+use adv_code_2025::direction::Direction;
+use adv_code_2025::generate::Day01;
+use adv_code_2025::{print_result, start_day};
 use anyhow::{Ok, Result, bail};
 use code_timing_macros::time_snippet;
 use const_format::concatcp;
@@ -36,11 +39,12 @@ fn main() -> Result<()> {
         let mut count = 0;
         for line in reader.lines().map_while(Result::ok) {
             let (dir, dist) = line.split_at(1);
+            let dir: Direction = dir.parse()?;
             let dist: i32 = dist.parse()?;
             match dir {
-                "L" => dial = (dial + 100 - dist) % 100,
-                "R" => dial = (dial + dist) % 100,
-                _ => bail!("Invalid direction"),
+                Direction::Left => dial = (dial + 100 - dist) % 100,
+                Direction::Right => dial = (dial + dist) % 100,
+                _ => bail!("Dial cannot turn {dir:?}"),
             }
             if dial == 0 {
                 count += 1;
@@ -53,7 +57,7 @@ fn main() -> Result<()> {
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part1(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 1, result);
     //endregion
 
     //region Part 2
@@ -65,9 +69,37 @@ fn main() -> Result<()> {
         let mut count = 0;
         for line in reader.lines().map_while(Result::ok) {
             let (dir, dist) = line.split_at(1);
+            let dir: Direction = dir.parse()?;
+            let dist: usize = dist.parse()?;
+            match dir {
+                Direction::Left => {
+                    // Distance to the first zero going left; a full turn when starting on zero.
+                    let first = if dial == 0 { 100 } else { dial };
+                    if dist >= first {
+                        count += (dist - first) / 100 + 1;
+                    }
+                    dial = (dial + 100 - dist % 100) % 100;
+                }
+                Direction::Right => {
+                    count += (dial + dist) / 100;
+                    dial = (dial + dist) % 100;
+                }
+                _ => bail!("Dial cannot turn {dir:?}"),
+            }
+        }
+        Ok(count)
+    }
+
+    #[allow(clippy::items_after_statements)]
+    fn part2_clicks<R: BufRead>(reader: R) -> Result<usize> {
+        let mut dial = 50;
+        let mut count = 0;
+        for line in reader.lines().map_while(Result::ok) {
+            let (dir, dist) = line.split_at(1);
+            let dir: Direction = dir.parse()?;
             let dist: i32 = dist.parse()?;
             match dir {
-                "L" => {
+                Direction::Left => {
                     for _ in 0..dist {
                         dial = (dial + 99) % 100;
                         if dial == 0 {
@@ -75,7 +107,7 @@ fn main() -> Result<()> {
                         }
                     }
                 }
-                "R" => {
+                Direction::Right => {
                     for _ in 0..dist {
                         dial = (dial + 1) % 100;
                         if dial == 0 {
@@ -83,17 +115,21 @@ fn main() -> Result<()> {
                         }
                     }
                 }
-                _ => bail!("Invalid direction"),
+                _ => bail!("Dial cannot turn {dir:?}"),
             }
         }
         Ok(count)
     }
 
     assert_eq!(6, part2(BufReader::new(TEST.as_bytes()))?);
+    Differential::new()
+        .register("arithmetic", |s| part2(BufReader::new(s.as_bytes())))
+        .register("clicks", |s| part2_clicks(BufReader::new(s.as_bytes())))
+        .check(&Day01, 50, 0..100)?;
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part2(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 2, result);
     //endregion
 
     Ok(())
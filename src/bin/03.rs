@@ -1,10 +1,13 @@
 #![allow(unexpected_cfgs)]
 #![warn(clippy::all, clippy::pedantic)]
 
-use adv_code_2025::start_day;
+use adv_code_2025::differential::Differential;
+use adv_code_2025::generate::Day03;
+use adv_code_2025::{print_result, start_day};
 use anyhow::{Ok, Result};
 use code_timing_macros::time_snippet;
 use const_format::concatcp;
+use itertools::Itertools;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 
@@ -47,7 +50,7 @@ fn main() -> Result<()> {
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part1(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 1, result);
     //endregion
 
     //region Part 2
@@ -84,11 +87,37 @@ fn main() -> Result<()> {
         Ok(total)
     }
 
+    #[allow(clippy::items_after_statements)]
+    fn part2_brute_force<R: BufRead>(reader: R) -> Result<u64> {
+        let mut total = 0u64;
+        for line in reader.lines().map_while(Result::ok) {
+            let digits: Vec<u8> = line.bytes().filter(u8::is_ascii_digit).collect();
+            let k = 12.min(digits.len());
+            let joltage = digits
+                .iter()
+                .combinations(k)
+                .map(|c| {
+                    c.iter()
+                        .fold(0u64, |acc, &&d| acc * 10 + u64::from(d - b'0'))
+                })
+                .max()
+                .unwrap_or(0);
+            total += joltage;
+        }
+        Ok(total)
+    }
+
     assert_eq!(3_121_910_778_619, part2(BufReader::new(TEST.as_bytes()))?);
+    Differential::new()
+        .register("monotonic stack", |s| part2(BufReader::new(s.as_bytes())))
+        .register("all combinations", |s| {
+            part2_brute_force(BufReader::new(s.as_bytes()))
+        })
+        .check(&Day03, 10, 0..20)?;
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part2(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 2, result);
     //endregion
 
     Ok(())
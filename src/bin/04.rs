@@ -1,12 +1,15 @@
 #![allow(unexpected_cfgs)]
 #![warn(clippy::all, clippy::pedantic)]
 
-use adv_code_2025::start_day;
+use adv_code_2025::automaton::{Automaton, Neighborhood, Run};
+use adv_code_2025::grid::Grid;
+use adv_code_2025::params::Params;
+use adv_code_2025::{print_result, start_day};
 use anyhow::{Ok, Result};
 use code_timing_macros::time_snippet;
 use const_format::concatcp;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader};
 
 const DAY: &str = "04";
 const INPUT_FILE: &str = concatcp!("input/", DAY, ".txt");
@@ -24,25 +27,34 @@ const TEST: &str = "\
 @.@.@@@.@.
 ";
 
+/// The round in which each roll of the example is removed.
+const EXAMPLE_HEATMAP: &str = "\
+..11.1121.
+134.2.2.32
+24578.1.33
+2.69@@..2.
+13.@@@@.21
+.24@@@@@.2
+.2.@.@.@@3
+1.4@@.@@@4
+.23@@@@@5.
+1.1.@@@.1.
+";
+
 fn main() -> Result<()> {
     start_day(DAY);
+    let params = Params::from_args()?;
 
     //region Part 1
     println!("=== Part 1 ===");
 
     #[allow(clippy::items_after_statements)]
     fn part1<R: BufRead>(reader: R) -> Result<usize> {
-        let grid = parse_grid(reader);
-        let h = grid.len();
-        let w = grid[0].len();
-        let mut accessible = 0;
-        for y in 0..h {
-            for x in 0..w {
-                if grid[y][x] == b'@' && adjacent_count(&grid, y, x) < 4 {
-                    accessible += 1;
-                }
-            }
-        }
+        let grid = parse_grid(reader)?;
+        let accessible = grid
+            .positions()
+            .filter(|&(x, y)| grid[(x, y)] && adjacent_count(&grid, x, y) < 4)
+            .count();
         Ok(accessible)
     }
 
@@ -50,7 +62,7 @@ fn main() -> Result<()> {
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part1(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 1, result);
     //endregion
 
     //region Part 2
@@ -58,65 +70,113 @@ fn main() -> Result<()> {
 
     #[allow(clippy::items_after_statements)]
     fn part2<R: BufRead>(reader: R) -> Result<usize> {
-        let mut grid = parse_grid(reader);
-        let h = grid.len();
-        let w = grid[0].len();
-        let mut total_removed = 0;
-        loop {
-            let mut to_remove = Vec::new();
-            for y in 0..h {
-                for x in 0..w {
-                    if grid[y][x] == b'@' && adjacent_count(&grid, y, x) < 4 {
-                        to_remove.push((y, x));
-                    }
-                }
-            }
-            if to_remove.is_empty() {
-                break;
-            }
-            for (y, x) in to_remove {
-                grid[y][x] = b'.';
-                total_removed += 1;
-            }
-        }
-        Ok(total_removed)
+        Ok(remove_rolls(parse_grid(reader)?).total_changes())
     }
 
     assert_eq!(43, part2(BufReader::new(TEST.as_bytes()))?);
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part2(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 2, result);
+    //endregion
+
+    //region Analysis
+    println!("\n=== Analysis ===");
+
+    let example = Analysis::new(parse_grid(BufReader::new(TEST.as_bytes()))?);
+    assert_eq!([13, 12, 7, 5, 2, 1, 1, 1, 1], example.wave_sizes()[..]);
+    assert_eq!(28, example.core_size());
+    assert_eq!(EXAMPLE_HEATMAP, example.heatmap());
+    assert_eq!(
+        "43 of 71 rolls removed in 9 rounds\nWave sizes: 13, 12, 7, 5, 2, 1, 1, 1, 1\n\
+         Never removed (4-core): 28\n",
+        example.summary()
+    );
+
+    let analysis = Analysis::new(parse_grid(BufReader::new(File::open(INPUT_FILE)?))?);
+    if params.get("heatmap", false)? {
+        print!("{}", analysis.heatmap());
+    }
+    print!("{}", analysis.summary());
     //endregion
 
     Ok(())
 }
 
-fn parse_grid<R: BufRead>(reader: R) -> Vec<Vec<u8>> {
-    reader
-        .lines()
-        .map_while(Result::ok)
-        .map(|line| line.bytes().collect())
-        .collect()
+fn parse_grid<R: BufRead>(reader: R) -> Result<Grid<bool>> {
+    Grid::parse_with(&io::read_to_string(reader)?, |c| Ok(c == '@'))
 }
 
-#[allow(clippy::cast_possible_wrap)]
-#[allow(clippy::cast_sign_loss)]
-fn adjacent_count(grid: &[Vec<u8>], y: usize, x: usize) -> usize {
-    let h = grid.len() as isize;
-    let w = grid[0].len() as isize;
-    let mut adj = 0;
-    for dy in -1..=1 {
-        for dx in -1..=1 {
-            if dy == 0 && dx == 0 {
-                continue;
-            }
-            let ny = y as isize + dy;
-            let nx = x as isize + dx;
-            if ny >= 0 && ny < h && nx >= 0 && nx < w && grid[ny as usize][nx as usize] == b'@' {
-                adj += 1;
+fn adjacent_count(grid: &Grid<bool>, x: usize, y: usize) -> usize {
+    grid.neighbors8(x, y).filter(|&p| grid[p]).count()
+}
+
+/// Removes accessible rolls round by round until none are left to remove.
+fn remove_rolls(grid: Grid<bool>) -> Run<bool> {
+    Automaton::new(Neighborhood::Moore, |&roll: &bool, neighbors: &[&bool]| {
+        (roll && neighbors.iter().filter(|&&&n| n).count() < 4).then_some(false)
+    })
+    .run(grid)
+}
+
+/// Which round removed each roll, and what is left at the end.
+struct Analysis {
+    initial: Grid<bool>,
+    run: Run<bool>,
+}
+
+impl Analysis {
+    fn new(grid: Grid<bool>) -> Self {
+        Self {
+            initial: grid.clone(),
+            run: remove_rolls(grid),
+        }
+    }
+
+    fn removed(&self) -> usize {
+        self.run.total_changes()
+    }
+
+    fn wave_sizes(&self) -> Vec<usize> {
+        self.run.changes.iter().map(Vec::len).collect()
+    }
+
+    /// Rolls that are never removed: every one of them keeps at least four
+    /// neighbours that are also never removed, i.e. the 4-core of the adjacency graph.
+    fn core_size(&self) -> usize {
+        self.run.grid.cells().iter().filter(|&&roll| roll).count()
+    }
+
+    /// The grid with each removed roll shown as its round in base 36 (`+` past 35),
+    /// `@` for rolls that stay and `.` for empty cells.
+    fn heatmap(&self) -> String {
+        let rounds = self.run.last_changed();
+        let mut out = String::new();
+        for (y, row) in self.initial.rows().enumerate() {
+            for (x, &roll) in row.iter().enumerate() {
+                out.push(match rounds[(x, y)] {
+                    _ if !roll => '.',
+                    None => '@',
+                    Some(round) => u32::try_from(round)
+                        .ok()
+                        .and_then(|r| char::from_digit(r, 36))
+                        .unwrap_or('+'),
+                });
             }
+            out.push('\n');
         }
+        out
+    }
+
+    fn summary(&self) -> String {
+        let waves: Vec<String> = self.wave_sizes().iter().map(ToString::to_string).collect();
+        let initial = self.initial.cells().iter().filter(|&&roll| roll).count();
+        format!(
+            "{} of {initial} rolls removed in {} rounds\nWave sizes: {}\nNever removed (4-core): {}\n",
+            self.removed(),
+            self.run.rounds(),
+            waves.join(", "),
+            self.core_size(),
+        )
     }
-    adj
 }
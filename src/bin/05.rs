@@ -1,7 +1,7 @@
 #![allow(unexpected_cfgs)]
 #![warn(clippy::all, clippy::pedantic)]
 
-use adv_code_2025::start_day;
+use adv_code_2025::{print_result, start_day};
 use anyhow::{Ok, Result, anyhow};
 use code_timing_macros::time_snippet;
 use const_format::concatcp;
@@ -50,7 +50,7 @@ fn main() -> Result<()> {
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part1(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 1, result);
     //endregion
 
     //region Part 2
@@ -81,7 +81,7 @@ fn main() -> Result<()> {
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part2(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 2, result);
     //endregion
 
     Ok(())
@@ -1,12 +1,14 @@
 #![allow(unexpected_cfgs)]
 #![warn(clippy::all, clippy::pedantic)]
 
-use adv_code_2025::start_day;
+use adv_code_2025::grid::Grid;
+use adv_code_2025::{print_result, start_day};
 use anyhow::{Context, Result};
 use code_timing_macros::time_snippet;
 use const_format::concatcp;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader};
+use std::ops::Range;
 
 const DAY: &str = "06";
 const INPUT_FILE: &str = concatcp!("input/", DAY, ".txt");
@@ -27,54 +29,16 @@ fn main() -> Result<()> {
 
     #[allow(clippy::items_after_statements)]
     fn part1<R: BufRead>(reader: R) -> Result<usize> {
-        let lines: Vec<String> = reader.lines().map_while(Result::ok).collect();
-        if lines.is_empty() {
+        let grid = Grid::parse_padded(&io::read_to_string(reader)?, ' ');
+        if grid.height() == 0 {
             return Ok(0);
         }
-        let height = lines.len();
-        let width = lines.iter().map(String::len).max().unwrap_or(0);
-        let grid: Vec<Vec<char>> = lines
-            .into_iter()
-            .map(|mut l| {
-                l.extend(std::iter::repeat_n(' ', width - l.len()));
-                l.chars().collect::<Vec<_>>()
-            })
-            .collect();
-        let mut columns: Vec<Vec<char>> = vec![vec![' '; height]; width];
-        for (y, row) in grid.iter().enumerate() {
-            for (x, &c) in row.iter().enumerate() {
-                columns[x][y] = c;
-            }
-        }
-        let mut problems: Vec<Vec<Vec<char>>> = Vec::new();
-        let mut current: Vec<Vec<char>> = Vec::new();
-        let mut in_space = false;
-        for col in columns {
-            if col.iter().all(|&c| c == ' ') {
-                if !in_space {
-                    if !current.is_empty() {
-                        problems.push(current);
-                        current = Vec::new();
-                    }
-                    in_space = true;
-                }
-            } else {
-                current.push(col);
-                in_space = false;
-            }
-        }
-        if !current.is_empty() {
-            problems.push(current);
-        }
+        let h = grid.height();
         let mut total = 0;
-        for problem in problems {
-            let h = problem[0].len();
-            let mut rows: Vec<String> = vec![String::new(); h];
-            for col in &problem {
-                for y in 0..h {
-                    rows[y].push(col[y]);
-                }
-            }
+        for range in problem_blocks(&grid) {
+            let rows: Vec<String> = (0..h)
+                .map(|y| grid.row(y)[range.clone()].iter().collect())
+                .collect();
             let op_row = rows.last().unwrap().trim();
             let op = op_row
                 .chars()
@@ -103,7 +67,7 @@ fn main() -> Result<()> {
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part1(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 1, result);
     //endregion
 
     //region Part 2
@@ -112,55 +76,19 @@ fn main() -> Result<()> {
     #[allow(clippy::items_after_statements)]
     #[allow(clippy::needless_range_loop)]
     fn part2<R: BufRead>(reader: R) -> Result<usize> {
-        let lines: Vec<String> = reader.lines().map_while(Result::ok).collect();
-        if lines.is_empty() {
+        let grid = Grid::parse_padded(&io::read_to_string(reader)?, ' ');
+        if grid.height() == 0 {
             return Ok(0);
         }
-
-        let height = lines.len();
-        let width = lines.iter().map(String::len).max().unwrap_or(0);
-
-        let grid: Vec<Vec<char>> = lines
-            .iter()
-            .map(|l| {
-                let mut chars: Vec<char> = l.chars().collect();
-                chars.resize(width, ' ');
-                chars
-            })
-            .collect();
-
-        let mut separator_indices = vec![];
-        for x in 0..width {
-            let is_empty = (0..height).all(|y| grid[y][x].is_whitespace());
-            if is_empty {
-                separator_indices.push(x);
-            }
-        }
-
-        let mut blocks = Vec::new();
-        let mut in_block = false;
-        let mut start_col = 0;
-
-        for x in 0..width {
-            let is_separator = separator_indices.contains(&x);
-            if !in_block && !is_separator {
-                in_block = true;
-                start_col = x;
-            } else if in_block && is_separator {
-                in_block = false;
-                blocks.push(start_col..x);
-            }
-        }
-        if in_block {
-            blocks.push(start_col..width);
-        }
+        let height = grid.height();
+        let blocks = problem_blocks(&grid);
 
         let mut grand_total: usize = 0;
 
         for range in blocks {
             let mut operator = ' ';
             for x in range.clone() {
-                let c = grid[height - 1][x];
+                let c = grid[(x, height - 1)];
                 if !c.is_whitespace() {
                     operator = c;
                     break;
@@ -173,7 +101,7 @@ fn main() -> Result<()> {
             for x in range.clone() {
                 let mut num_str = String::new();
                 for y in 0..height - 1 {
-                    let c = grid[y][x];
+                    let c = grid[(x, y)];
                     if !c.is_whitespace() {
                         num_str.push(c);
                     }
@@ -207,8 +135,29 @@ fn main() -> Result<()> {
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part2(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 2, result);
     //endregion
 
     Ok(())
 }
+
+/// Column ranges of the problems, which are separated by columns of blanks.
+fn problem_blocks(grid: &Grid<char>) -> Vec<Range<usize>> {
+    let mut blocks = Vec::new();
+    let mut start = None;
+    for x in 0..grid.width() {
+        let is_separator = grid.column(x).all(|c| c.is_whitespace());
+        match (start, is_separator) {
+            (None, false) => start = Some(x),
+            (Some(s), true) => {
+                blocks.push(s..x);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        blocks.push(s..grid.width());
+    }
+    blocks
+}
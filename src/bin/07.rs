@@ -1,13 +1,16 @@
 #![allow(unexpected_cfgs)]
 #![warn(clippy::all, clippy::pedantic)]
 
-use adv_code_2025::start_day;
+use adv_code_2025::direction::Direction;
+use adv_code_2025::grid::Grid;
+use adv_code_2025::point::Point2;
+use adv_code_2025::{print_result, start_day};
 use anyhow::{Ok, Result};
 use code_timing_macros::time_snippet;
 use const_format::concatcp;
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader};
 
 const DAY: &str = "07";
 const INPUT_FILE: &str = concatcp!("input/", DAY, ".txt");
@@ -39,34 +42,21 @@ fn main() -> Result<()> {
 
     #[allow(clippy::items_after_statements)]
     fn part1<R: BufRead>(reader: R) -> Result<usize> {
-        let grid: Vec<Vec<char>> = reader
-            .lines()
-            .map_while(Result::ok)
-            .map(|line| line.chars().collect())
-            .collect();
-        let height = grid.len();
-        let width = grid[0].len();
-        let start_x = grid[0].iter().position(|&c| c == 'S').expect("No S found");
+        let grid = Grid::parse(&io::read_to_string(reader)?)?;
+        let start = Point2::from(grid.find(&'S').expect("No S found"));
         let mut split_positions = HashSet::new();
-        let mut queue = vec![(start_x, 1)];
-        while let Some((x, y)) = queue.pop() {
-            if y >= height {
-                continue;
-            }
-            match grid[y][x] {
-                '^' => {
-                    if split_positions.insert((x, y)) {
-                        if x > 0 {
-                            queue.push((x - 1, y + 1));
-                        }
-                        if x + 1 < width {
-                            queue.push((x + 1, y + 1));
-                        }
+        let mut queue: Vec<_> = grid.step(start, Direction::Down).into_iter().collect();
+        while let Some(p) = queue.pop() {
+            match grid[p] {
+                '^' if split_positions.insert(p) => {
+                    for side in [Direction::Left, Direction::Right] {
+                        queue.extend(
+                            grid.step(p, side)
+                                .and_then(|q| grid.step(q, Direction::Down)),
+                        );
                     }
                 }
-                '.' | 'S' => {
-                    queue.push((x, y + 1));
-                }
+                '.' | 'S' => queue.extend(grid.step(p, Direction::Down)),
                 _ => {}
             }
         }
@@ -77,7 +67,7 @@ fn main() -> Result<()> {
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part1(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 1, result);
     //endregion
 
     //region Part 2
@@ -85,43 +75,37 @@ fn main() -> Result<()> {
 
     #[allow(clippy::items_after_statements)]
     fn part2<R: BufRead>(reader: R) -> Result<usize> {
-        let grid: Vec<Vec<char>> = reader
-            .lines()
-            .map_while(Result::ok)
-            .map(|line| line.chars().collect())
-            .collect();
-        let start_x = grid[0].iter().position(|&c| c == 'S').expect("No S found");
+        let grid = Grid::parse(&io::read_to_string(reader)?)?;
+        let start = Point2::from(grid.find(&'S').expect("No S found"));
         fn dfs(
-            x: usize,
-            y: usize,
-            grid: &Vec<Vec<char>>,
-            memo: &mut HashMap<(usize, usize), usize>,
+            p: Point2<usize>,
+            grid: &Grid<char>,
+            memo: &mut HashMap<Point2<usize>, usize>,
         ) -> usize {
-            if y == grid.len() - 1 {
+            if p.y == grid.height() - 1 {
                 return 1;
             }
-            if let Some(&cached) = memo.get(&(x, y)) {
+            if let Some(&cached) = memo.get(&p) {
                 return cached;
             }
-            let res = match grid[y][x] {
-                '^' => {
-                    let mut sum = 0;
-                    if x > 0 {
-                        sum += dfs(x - 1, y + 1, grid, memo);
-                    }
-                    if x + 1 < grid[0].len() {
-                        sum += dfs(x + 1, y + 1, grid, memo);
-                    }
-                    sum
-                }
-                '.' | 'S' => dfs(x, y + 1, grid, memo),
+            let res = match grid[p] {
+                '^' => [Direction::Left, Direction::Right]
+                    .into_iter()
+                    .filter_map(|side| grid.step(grid.step(p, side)?, Direction::Down))
+                    .map(|q| dfs(q, grid, memo))
+                    .sum(),
+                '.' | 'S' => grid
+                    .step(p, Direction::Down)
+                    .map_or(0, |q| dfs(q, grid, memo)),
                 _ => 0,
             };
-            memo.insert((x, y), res);
+            memo.insert(p, res);
             res
         }
         let mut memo = HashMap::new();
-        let result = dfs(start_x, 1, &grid, &mut memo);
+        let result = grid
+            .step(start, Direction::Down)
+            .map_or(0, |q| dfs(q, &grid, &mut memo));
         Ok(result)
     }
 
@@ -129,7 +113,7 @@ fn main() -> Result<()> {
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part2(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 2, result);
     //endregion
 
     Ok(())
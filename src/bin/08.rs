@@ -1,7 +1,13 @@
 #![allow(unexpected_cfgs)]
 #![warn(clippy::all, clippy::pedantic)]
 
-use adv_code_2025::start_day;
+use adv_code_2025::clustering::{Clustering, Edge};
+use adv_code_2025::differential::Differential;
+use adv_code_2025::generate::Day08;
+use adv_code_2025::kdtree::KdTree;
+use adv_code_2025::params::Params;
+use adv_code_2025::point::Point3;
+use adv_code_2025::{print_result, start_day};
 use anyhow::{Ok, Result};
 use code_timing_macros::time_snippet;
 use const_format::concatcp;
@@ -36,116 +42,109 @@ const TEST: &str = "\
 
 fn main() -> Result<()> {
     start_day(DAY);
+    let params = Params::from_args()?;
 
     //region Part 1
     println!("=== Part 1 ===");
 
     #[allow(clippy::items_after_statements)]
-    fn part1<R: BufRead>(reader: R) -> Result<usize> {
-        let coords = parse_coords(reader);
-        let n = coords.len();
-        let mut edges = generate_edges(&coords);
-        edges.sort_unstable_by_key(|e| e.0);
-        let mut parent: Vec<usize> = (0..n).collect();
-        let mut size = vec![1; n];
-        let limit = if n <= 20 { 10 } else { 1000 };
-        for &(_dist2, i, j) in edges.iter().take(limit) {
-            let pi = find(&mut parent, i);
-            let pj = find(&mut parent, j);
-            if pi != pj {
-                parent[pi] = pj;
-                size[pj] += size[pi];
-                size[pi] = 0;
-            }
-        }
-        let mut sizes: Vec<usize> = size.into_iter().filter(|&s| s > 0).collect();
-        sizes.sort_unstable_by(|a, b| b.cmp(a));
-        let answer = sizes.iter().take(3).product();
-        Ok(answer)
+    fn part1<R: BufRead>(reader: R, params: &Params) -> Result<usize> {
+        let coords = parse_coords(reader)?;
+        let limit = params.get("pairs", 1000)?;
+        let tree = KdTree::new(&coords);
+        Ok(largest_circuits(coords.len(), tree.pairs(), limit))
+    }
+
+    #[allow(clippy::items_after_statements)]
+    fn part1_all_pairs<R: BufRead>(reader: R, params: &Params) -> Result<usize> {
+        let coords = parse_coords(reader)?;
+        let limit = params.get("pairs", 1000)?;
+        Ok(largest_circuits(coords.len(), all_pairs(&coords), limit))
     }
 
-    assert_eq!(40, part1(BufReader::new(TEST.as_bytes()))?);
+    let example = Params::new().with("pairs", 10);
+    assert_eq!(40, part1(BufReader::new(TEST.as_bytes()), &example)?);
+    let pairs = Params::new().with("pairs", 300);
+    Differential::new()
+        .register("lazy pairs", |s| {
+            part1(BufReader::new(s.as_bytes()), &pairs)
+        })
+        .register("all pairs", |s| {
+            part1_all_pairs(BufReader::new(s.as_bytes()), &pairs)
+        })
+        .check(&Day08, 200, 0..10)?;
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
-    let result = time_snippet!(part1(input_file)?);
-    println!("Result = {result}");
+    let result = time_snippet!(part1(input_file, &params)?);
+    print_result(DAY, 1, result);
     //endregion
 
     //region Part 2
     println!("\n=== Part 2 ===");
 
-    #[allow(clippy::cast_sign_loss)]
     #[allow(clippy::items_after_statements)]
     fn part2<R: BufRead>(reader: R) -> Result<usize> {
-        let coords = parse_coords(reader);
-        let n = coords.len();
-        if n < 2 {
-            return Ok(0);
-        }
-        let mut edges = generate_edges(&coords);
-        edges.sort_unstable_by_key(|e| e.0);
-        let mut parent: Vec<usize> = (0..n).collect();
-        let mut components = n;
-        for &(_dist, u, v) in &edges {
-            let root_u = find(&mut parent, u);
-            let root_v = find(&mut parent, v);
-            if root_u != root_v {
-                parent[root_u] = root_v;
-                components -= 1;
-                if components == 1 {
-                    let ans = (coords[u].0 as usize) * (coords[v].0 as usize);
-                    return Ok(ans);
-                }
-            }
-        }
-        Ok(0)
+        let coords = parse_coords(reader)?;
+        let tree = KdTree::new(&coords);
+        Ok(last_connection(&coords, tree.pairs()))
+    }
+
+    #[allow(clippy::items_after_statements)]
+    fn part2_all_pairs<R: BufRead>(reader: R) -> Result<usize> {
+        let coords = parse_coords(reader)?;
+        Ok(last_connection(&coords, all_pairs(&coords)))
     }
 
     assert_eq!(25272, part2(BufReader::new(TEST.as_bytes()))?);
+    Differential::new()
+        .register("lazy pairs", |s| part2(BufReader::new(s.as_bytes())))
+        .register("all pairs", |s| {
+            part2_all_pairs(BufReader::new(s.as_bytes()))
+        })
+        .check(&Day08, 200, 0..10)?;
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part2(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 2, result);
     //endregion
 
     Ok(())
 }
 
-fn parse_coords<R: BufRead>(reader: R) -> Vec<(i32, i32, i32)> {
+fn parse_coords<R: BufRead>(reader: R) -> Result<Vec<Point3>> {
     reader
         .lines()
         .map_while(Result::ok)
         .filter(|l| !l.trim().is_empty())
-        .map(|l| {
-            let mut parts = l.split(',');
-            let x = parts.next().unwrap().trim().parse().unwrap();
-            let y = parts.next().unwrap().trim().parse().unwrap();
-            let z = parts.next().unwrap().trim().parse().unwrap();
-            (x, y, z)
-        })
+        .map(|l| l.parse())
         .collect()
 }
 
-fn find(parent: &mut [usize], x: usize) -> usize {
-    if parent[x] != x {
-        parent[x] = find(parent, parent[x]);
-    }
-    parent[x]
+/// Product of the three largest circuits after connecting the `limit` shortest pairs.
+fn largest_circuits(n: usize, pairs: impl Iterator<Item = Edge<u128>>, limit: usize) -> usize {
+    let mut circuits = Clustering::from_sorted(n, pairs);
+    circuits.take_edges(limit);
+    circuits.sets().component_sizes().iter().take(3).product()
+}
+
+/// Product of the X coordinates of the pair that joins everything into one circuit.
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+fn last_connection(coords: &[Point3], pairs: impl Iterator<Item = Edge<u128>>) -> usize {
+    let last = Clustering::from_sorted(coords.len(), pairs).find(|m| m.components == 1);
+    last.map_or(0, |m| (coords[m.a].x as usize) * (coords[m.b].x as usize))
 }
 
-fn generate_edges(coords: &[(i32, i32, i32)]) -> Vec<(i64, usize, usize)> {
+/// Every pair of points in `(distance, i, j)` order, by sorting them all up front.
+fn all_pairs(coords: &[Point3]) -> std::vec::IntoIter<Edge<u128>> {
     let n = coords.len();
-    let mut edges = Vec::with_capacity(n * (n - 1) / 2);
+    let mut edges = Vec::with_capacity(n * n.saturating_sub(1) / 2);
     for i in 0..n {
         for j in i + 1..n {
-            let (x1, y1, z1) = coords[i];
-            let (x2, y2, z2) = coords[j];
-            let dx = i64::from(x1 - x2);
-            let dy = i64::from(y1 - y2);
-            let dz = i64::from(z1 - z2);
-            let dist2 = dx * dx + dy * dy + dz * dz;
+            let dist2 = coords[i].squared_euclidean(coords[j]);
             edges.push((dist2, i, j));
         }
     }
-    edges
+    edges.sort_unstable();
+    edges.into_iter()
 }
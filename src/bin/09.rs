@@ -1,8 +1,10 @@
 #![allow(unexpected_cfgs)]
 #![warn(clippy::all, clippy::pedantic)]
 
-use adv_code_2025::start_day;
-use anyhow::{Ok, Result};
+use adv_code_2025::point::Point2;
+use adv_code_2025::sparse_grid::SparseGrid;
+use adv_code_2025::{print_result, start_day};
+use anyhow::{Ok, Result, bail};
 use code_timing_macros::time_snippet;
 use const_format::concatcp;
 use std::fs::File;
@@ -22,27 +24,33 @@ const TEST: &str = "\
 7,3
 ";
 
+/// The example's loop of red (`#`) and green (`X`) tiles, as drawn in the puzzle.
+const TEST_LOOP: &str = "\
+..............
+.......#XXX#..
+.......X...X..
+..#XXXX#...X..
+..X........X..
+..#XXXXXX#.X..
+.........X.X..
+.........#X#..
+..............
+";
+
 fn main() -> Result<()> {
     start_day(DAY);
 
     //region Part 1
     println!("=== Part 1 ===");
 
-    #[allow(clippy::cast_possible_truncation)]
     #[allow(clippy::items_after_statements)]
     fn part1<R: BufRead>(reader: R) -> Result<usize> {
-        let tiles = parse_tiles(reader);
+        let tiles = parse_tiles(reader)?;
         let mut max_area = 0;
 
         for i in 0..tiles.len() {
             for j in (i + 1)..tiles.len() {
-                let (x1, y1) = tiles[i];
-                let (x2, y2) = tiles[j];
-
-                let width = (x1 - x2).unsigned_abs() as usize + 1;
-                let height = (y1 - y2).unsigned_abs() as usize + 1;
-                let area = width * height;
-
+                let area = rectangle_area(tiles[i], tiles[j]);
                 if area > max_area {
                     max_area = area;
                 }
@@ -55,17 +63,15 @@ fn main() -> Result<()> {
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part1(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 1, result);
     //endregion
 
     //region Part 2
     println!("\n=== Part 2 ===");
 
-    #[allow(clippy::cast_possible_truncation)]
     #[allow(clippy::items_after_statements)]
-    #[allow(clippy::similar_names)]
     fn part2<R: BufRead>(reader: R) -> Result<usize> {
-        let tiles = parse_tiles(reader);
+        let tiles = parse_tiles(reader)?;
         let mut max_area = 0;
 
         // Build edges for the polygon
@@ -78,13 +84,7 @@ fn main() -> Result<()> {
 
         for i in 0..tiles.len() {
             for j in (i + 1)..tiles.len() {
-                let (x1, y1) = tiles[i];
-                let (x2, y2) = tiles[j];
-
-                // Calculate dimensions
-                let width = (x1 - x2).unsigned_abs() as usize + 1;
-                let height = (y1 - y2).unsigned_abs() as usize + 1;
-                let area = width * height;
+                let area = rectangle_area(tiles[i], tiles[j]);
 
                 // Optimization: Don't check complex geometry if area is already smaller than max
                 if area <= max_area {
@@ -93,12 +93,10 @@ fn main() -> Result<()> {
 
                 // Check if this rectangle is valid (entirely inside/on polygon)
                 // Rectangle Bounds (inclusive)
-                let rx_min = x1.min(x2);
-                let rx_max = x1.max(x2);
-                let ry_min = y1.min(y2);
-                let ry_max = y1.max(y2);
+                let min = tiles[i].component_min(tiles[j]);
+                let max = tiles[i].component_max(tiles[j]);
 
-                if is_valid_rectangle(rx_min, rx_max, ry_min, ry_max, &edges) {
+                if is_valid_rectangle(min.x, max.x, min.y, max.y, &edges) {
                     max_area = area;
                 }
             }
@@ -106,32 +104,62 @@ fn main() -> Result<()> {
         Ok(max_area)
     }
 
+    let test_loop = tile_loop(&parse_tiles(BufReader::new(TEST.as_bytes()))?)?;
+    assert_eq!(
+        TEST_LOOP,
+        test_loop.render_viewport(Point2::new(0, 0), Point2::new(13, 8), |tile| {
+            tile.copied().unwrap_or('.')
+        })
+    );
     assert_eq!(24, part2(BufReader::new(TEST.as_bytes()))?);
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part2(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 2, result);
     //endregion
 
     Ok(())
 }
 
-fn parse_tiles<R: BufRead>(reader: R) -> Vec<(i64, i64)> {
+fn parse_tiles<R: BufRead>(reader: R) -> Result<Vec<Point2>> {
     reader
         .lines()
         .map_while(Result::ok)
         .filter(|line| !line.is_empty())
-        .map(|line| {
-            let (x_str, y_str) = line.split_once(',').expect("Line must contain a comma");
-            (
-                x_str.trim().parse().expect("Invalid X coordinate"),
-                y_str.trim().parse().expect("Invalid Y coordinate"),
-            )
-        })
+        .map(|line| line.parse())
         .collect()
 }
 
-type Edge = ((i64, i64), (i64, i64));
+/// Red tiles as `#` and the green tiles joining each one to the next as `X`.
+/// Stores every tile on the loop, so it is for drawing small inputs.
+fn tile_loop(tiles: &[Point2]) -> Result<SparseGrid<char>> {
+    let mut grid = SparseGrid::new();
+    for (i, &from) in tiles.iter().enumerate() {
+        let to = tiles[(i + 1) % tiles.len()];
+        if from.x != to.x && from.y != to.y {
+            bail!("Red tiles {from:?} and {to:?} are not in the same row or column");
+        }
+        let step = Point2::new((to.x - from.x).signum(), (to.y - from.y).signum());
+        let mut tile = from;
+        while tile != to {
+            grid.insert(tile, 'X');
+            tile += step;
+        }
+    }
+    for &tile in tiles {
+        grid.insert(tile, '#');
+    }
+    Ok(grid)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn rectangle_area(a: Point2, b: Point2) -> usize {
+    let width = a.x.abs_diff(b.x) as usize + 1;
+    let height = a.y.abs_diff(b.y) as usize + 1;
+    width * height
+}
+
+type Edge = (Point2, Point2);
 
 #[allow(clippy::cast_possible_truncation)]
 #[allow(clippy::cast_precision_loss)]
@@ -145,7 +173,7 @@ fn is_valid_rectangle(
 ) -> bool {
     // 1. Check if any polygon edge splits the rectangle
     // A split happens if an edge passes strictly through the interior of the rect.
-    for &((px1, py1), (px2, py2)) in edges {
+    for &(Point2 { x: px1, y: py1 }, Point2 { x: px2, y: py2 }) in edges {
         let ex_min = px1.min(px2);
         let ex_max = px1.max(px2);
         let ey_min = py1.min(py2);
@@ -186,7 +214,7 @@ fn is_valid_rectangle(
     // Check "On Boundary" (distance to any edge is 0)
     // Actually, simple check: is cx, cy exactly on any segment?
     // Since edges are axis aligned, this is easy.
-    for &((px1, py1), (px2, py2)) in edges {
+    for &(Point2 { x: px1, y: py1 }, Point2 { x: px2, y: py2 }) in edges {
         let ex_min = px1.min(px2) as f64;
         let ex_max = px1.max(px2) as f64;
         let ey_min = py1.min(py2) as f64;
@@ -204,7 +232,7 @@ fn is_valid_rectangle(
 
     // Ray Casting (Ray to x = +infinity)
     let mut intersections = 0;
-    for &((px1, py1), (px2, py2)) in edges {
+    for &(Point2 { x: px1, y: py1 }, Point2 { x: px2, y: py2 }) in edges {
         let is_vertical = px1 == px2;
         if is_vertical {
             let vx = px1 as f64;
@@ -1,13 +1,14 @@
 #![allow(unexpected_cfgs)]
 #![warn(clippy::all, clippy::pedantic)]
 
-use adv_code_2025::start_day;
+use adv_code_2025::graph::Graph;
+use adv_code_2025::graph::paths::{count_paths, count_paths_via};
+use adv_code_2025::{print_result, start_day};
 use anyhow::{Ok, Result};
 use code_timing_macros::time_snippet;
 use const_format::concatcp;
-use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader};
 
 const DAY: &str = "11";
 const INPUT_FILE: &str = concatcp!("input/", DAY, ".txt");
@@ -49,9 +50,8 @@ fn main() -> Result<()> {
 
     #[allow(clippy::items_after_statements)]
     fn part1<R: BufRead>(reader: R) -> Result<usize> {
-        let graph = parse_graph(reader)?;
-        let mut memo = HashMap::new();
-        let answer = count_paths("you", "out", &graph, &mut memo);
+        let graph = Graph::parse(&io::read_to_string(reader)?)?;
+        let answer = count_paths(&graph, graph.id("you")?, graph.id("out")?)?;
         Ok(answer)
     }
 
@@ -59,7 +59,7 @@ fn main() -> Result<()> {
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part1(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 1, result);
     //endregion
 
     //region Part 2
@@ -67,82 +67,22 @@ fn main() -> Result<()> {
 
     #[allow(clippy::items_after_statements)]
     fn part2<R: BufRead>(reader: R) -> Result<usize> {
-        let graph = parse_graph(reader)?;
+        let graph = Graph::parse(&io::read_to_string(reader)?)?;
+        let svr = graph.id("svr")?;
+        let dac = graph.id("dac")?;
+        let fft = graph.id("fft")?;
+        let out = graph.id("out")?;
 
-        // Helper to run path counting with a fresh memo cache every time
-        let run_count = |start, end| -> usize {
-            let mut memo = HashMap::new();
-            count_paths(start, end, &graph, &mut memo)
-        };
-
-        // Case A: Path goes svr -> ... -> dac -> ... -> fft -> ... -> out
-        // Count = (svr->dac) * (dac->fft) * (fft->out)
-        let svr_dac = run_count("svr", "dac");
-        let dac_fft = run_count("dac", "fft");
-        let fft_out = run_count("fft", "out");
-        let path_dac_first = svr_dac * dac_fft * fft_out;
-
-        // Case B: Path goes svr -> ... -> fft -> ... -> dac -> ... -> out
-        // Count = (svr->fft) * (fft->dac) * (dac->out)
-        let svr_fft = run_count("svr", "fft");
-        let fft_dac = run_count("fft", "dac");
-        let dac_out = run_count("dac", "out");
-        let path_fft_first = svr_fft * fft_dac * dac_out;
-
-        Ok(path_dac_first + path_fft_first)
+        let answer = count_paths_via(&graph, svr, out, &[dac, fft])?;
+        Ok(answer)
     }
 
     assert_eq!(2, part2(BufReader::new(TEST2.as_bytes()))?);
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part2(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 2, result);
     //endregion
 
     Ok(())
 }
-
-fn parse_graph<R: BufRead>(reader: R) -> Result<HashMap<String, Vec<String>>> {
-    let mut graph: HashMap<String, Vec<String>> = HashMap::new();
-    for line in reader.lines() {
-        let line = line?;
-        if line.trim().is_empty() {
-            continue;
-        }
-        if let Some((source, destinations)) = line.split_once(": ") {
-            let dest_nodes: Vec<String> = destinations
-                .split_whitespace()
-                .map(ToString::to_string)
-                .collect();
-            graph.insert(source.to_string(), dest_nodes);
-        }
-    }
-    Ok(graph)
-}
-
-fn count_paths(
-    current_node: &str,
-    target_node: &str,
-    graph: &HashMap<String, Vec<String>>,
-    memo: &mut HashMap<String, usize>,
-) -> usize {
-    if current_node == target_node {
-        return 1;
-    }
-
-    if let Some(&count) = memo.get(current_node) {
-        return count;
-    }
-
-    let Some(neighbors) = graph.get(current_node) else {
-        return 0;
-    };
-
-    let mut total_paths = 0;
-    for neighbor in neighbors {
-        total_paths += count_paths(neighbor, target_node, graph, memo);
-    }
-
-    memo.insert(current_node.to_string(), total_paths);
-    total_paths
-}
@@ -1,8 +1,10 @@
 #![allow(unexpected_cfgs)]
 #![warn(clippy::all, clippy::pedantic)]
 
-use adv_code_2025::start_day;
-use anyhow::{Ok, Result};
+use adv_code_2025::grid::Grid;
+use adv_code_2025::point::Point2;
+use adv_code_2025::{print_result, start_day};
+use anyhow::{Context, Ok, Result};
 use code_timing_macros::time_snippet;
 use const_format::concatcp;
 use std::collections::{HashMap, HashSet};
@@ -48,15 +50,9 @@ const TEST: &str = "\
 12x5: 1 0 1 0 3 2
 ";
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
-struct Point {
-    r: i32,
-    c: i32,
-}
-
 #[derive(Clone, Debug)]
 struct Variation {
-    points: Vec<Point>,
+    points: Vec<Point2<usize>>,
     width: usize,
     height: usize,
 }
@@ -100,7 +96,7 @@ fn main() -> Result<()> {
 
                 if header.contains('x') {
                     if let Some(id) = current_id {
-                        shapes.insert(id, parse_shape(&current_rows));
+                        shapes.insert(id, parse_shape(&current_rows)?);
                         current_id = None;
                         current_rows.clear();
                     }
@@ -124,7 +120,7 @@ fn main() -> Result<()> {
                     queries.push(Query { w, h, presents });
                 } else {
                     if let Some(id) = current_id {
-                        shapes.insert(id, parse_shape(&current_rows));
+                        shapes.insert(id, parse_shape(&current_rows)?);
                         current_rows.clear();
                     }
                     current_id = Some(header.parse()?);
@@ -134,7 +130,7 @@ fn main() -> Result<()> {
             }
         }
         if let Some(id) = current_id {
-            shapes.insert(id, parse_shape(&current_rows));
+            shapes.insert(id, parse_shape(&current_rows)?);
         }
 
         let mut solved_count = 0;
@@ -142,8 +138,8 @@ fn main() -> Result<()> {
             let mut presents = q.presents.clone();
             presents.sort_by(|a, b| shapes[b].area.cmp(&shapes[a].area));
 
-            let mut grid = vec![false; q.w * q.h];
-            if solve_query(&mut grid, q.w, q.h, &presents, &shapes, q.w * q.h, 0) {
+            let mut grid = Grid::new(q.w, q.h, false);
+            if solve_query(&mut grid, &presents, &shapes, q.w * q.h, 0) {
                 solved_count += 1;
             }
         }
@@ -155,83 +151,51 @@ fn main() -> Result<()> {
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
     let result = time_snippet!(part1(input_file)?);
-    println!("Result = {result}");
+    print_result(DAY, 1, result);
     //endregion
 
     Ok(())
 }
 
-#[allow(clippy::cast_possible_truncation)]
-#[allow(clippy::cast_possible_wrap)]
-#[allow(clippy::cast_sign_loss)]
-#[allow(clippy::items_after_statements)]
-fn parse_shape(lines: &[String]) -> Shape {
-    let mut base_points = Vec::new();
-    for (r, line) in lines.iter().enumerate() {
-        for (c, ch) in line.chars().enumerate() {
-            if ch == '#' {
-                base_points.push(Point {
-                    r: r as i32,
-                    c: c as i32,
-                });
-            }
-        }
-    }
-
-    let area = base_points.len();
+fn parse_shape(lines: &[String]) -> Result<Shape> {
+    let base = Grid::parse_with(&lines.join("\n"), |c| Ok(c == '#'))?;
+    let area = base.find_all(&true).count();
     let mut variations = Vec::new();
     let mut seen = HashSet::new();
 
-    // Generate all 8 symmetries
-    for i in 0..8 {
-        let mut points: Vec<Point> = base_points.clone();
-
-        // Flip
-        if i >= 4 {
-            for p in &mut points {
-                p.c = -p.c;
-            }
-        }
-        // Rotate
-        for _ in 0..(i % 4) {
-            for p in &mut points {
-                let tmp = p.r;
-                p.r = p.c;
-                p.c = -tmp;
+    // Generate all 8 symmetries: four rotations, unflipped and flipped
+    for mut grid in [base.clone(), base.flip_horizontal()] {
+        for _ in 0..4 {
+            let points: Vec<Point2<usize>> = grid.find_all(&true).map(Point2::from).collect();
+            grid = grid.rotate_cw();
+
+            // Normalize
+            let min = points
+                .iter()
+                .copied()
+                .reduce(Point2::component_min)
+                .context("Shape has no cells")?;
+            let mut normalized: Vec<Point2<usize>> = points.iter().map(|&p| p - min).collect();
+            normalized.sort();
+
+            if seen.insert(normalized.clone()) {
+                let max = normalized
+                    .iter()
+                    .fold(Point2::new(0, 0), |acc, &p| acc.component_max(p));
+                variations.push(Variation {
+                    points: normalized,
+                    height: max.y + 1,
+                    width: max.x + 1,
+                });
             }
         }
-
-        // Normalize
-        let min_r = points.iter().map(|p| p.r).min().unwrap_or(0);
-        let min_c = points.iter().map(|p| p.c).min().unwrap_or(0);
-        let mut normalized: Vec<Point> = points
-            .iter()
-            .map(|p| Point {
-                r: p.r - min_r,
-                c: p.c - min_c,
-            })
-            .collect();
-        normalized.sort();
-
-        if seen.insert(normalized.clone()) {
-            let max_r = normalized.iter().map(|p| p.r).max().unwrap_or(0);
-            let max_c = normalized.iter().map(|p| p.c).max().unwrap_or(0);
-            variations.push(Variation {
-                points: normalized,
-                height: (max_r + 1) as usize,
-                width: (max_c + 1) as usize,
-            });
-        }
     }
 
-    Shape { variations, area }
+    Ok(Shape { variations, area })
 }
 
-#[allow(clippy::cast_sign_loss)]
 fn solve_query(
-    grid: &mut Vec<bool>,
-    w: usize,
-    h: usize,
+    grid: &mut Grid<bool>,
     presents: &[usize],
     shapes: &HashMap<usize, Shape>,
     empty_cells: usize,
@@ -251,6 +215,8 @@ fn solve_query(
         return false;
     }
 
+    let (w, h) = (grid.width(), grid.height());
+
     for i in start_idx..(w * h) {
         let r = i / w;
         let c = i % w;
@@ -266,8 +232,7 @@ fn solve_query(
 
             let mut fits = true;
             for p in &var.points {
-                let idx = (r + p.r as usize) * w + (c + p.c as usize);
-                if grid[idx] {
+                if grid[(c + p.x, r + p.y)] {
                     fits = false;
                     break;
                 }
@@ -275,8 +240,7 @@ fn solve_query(
 
             if fits {
                 for p in &var.points {
-                    let idx = (r + p.r as usize) * w + (c + p.c as usize);
-                    grid[idx] = true;
+                    grid[(c + p.x, r + p.y)] = true;
                 }
 
                 let next_start = if presents.len() > 1 && presents[1] == pid {
@@ -287,8 +251,6 @@ fn solve_query(
 
                 if solve_query(
                     grid,
-                    w,
-                    h,
                     &presents[1..],
                     shapes,
                     empty_cells - shape.area,
@@ -298,8 +260,7 @@ fn solve_query(
                 }
 
                 for p in &var.points {
-                    let idx = (r + p.r as usize) * w + (c + p.c as usize);
-                    grid[idx] = false;
+                    grid[(c + p.x, r + p.y)] = false;
                 }
             }
         }
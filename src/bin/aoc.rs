@@ -0,0 +1,328 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use adv_code_2025::DAYS;
+use adv_code_2025::answer::Answer;
+use adv_code_2025::generate::generator;
+use adv_code_2025::graph::Graph;
+use adv_code_2025::graph::dominators::must_pass;
+use adv_code_2025::graph::dot::{Dot, circuits};
+use adv_code_2025::graph::flow::{edge_disjoint_paths, node_disjoint_paths};
+use adv_code_2025::graph::paths::count_paths;
+use adv_code_2025::leaderboard::Leaderboard;
+use adv_code_2025::point::Point3;
+use adv_code_2025::progress::{self, PROGRESS_FILE, Progress};
+use adv_code_2025::rng::Rng;
+use anyhow::{Context, Result, anyhow, bail};
+use std::collections::HashMap;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const USAGE: &str = "\
+Usage: aoc <command> [args]
+
+Commands:
+  status                       Show the star calendar and solved parts
+  record <day> <part> <answer> Mark a part as solved with the given answer
+  leaderboard <file.json>      Rank a saved private leaderboard and chart its stars
+  gen <day> [--size N] [--seed S] [--out FILE]
+                               Write a random input for the day
+  dominators [file] [--from A] [--to B] [--through NODE]
+                               List the nodes on every path from A to B (day 11 by default)
+  disjoint [file] [--from A] [--to B]
+                               Count disjoint paths from A to B and the smallest cut
+  dot [file] [--day 11] [--from A] [--to B] [--highlight A,B,..] [--out FILE]
+                               Write the day 11 graph as Graphviz DOT with path counts on edges
+  dot [file] --day 08 [--pairs N] [--out FILE]
+                               Write day 08's circuits as Graphviz DOT, one colour per circuit
+";
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(command) = args.first() else {
+        print!("{USAGE}");
+        return Ok(());
+    };
+    match command.as_str() {
+        "status" => status(),
+        "record" => record(&args[1..]),
+        "leaderboard" => leaderboard(&args[1..]),
+        "gen" => gen_input(&args[1..]),
+        "dominators" => dominators(&args[1..]),
+        "disjoint" => disjoint(&args[1..]),
+        "dot" => dot(&args[1..]),
+        "help" | "--help" | "-h" => {
+            print!("{USAGE}");
+            Ok(())
+        }
+        _ => bail!("Unknown command: {command}\n\n{USAGE}"),
+    }
+}
+
+fn status() -> Result<()> {
+    let progress = Progress::load(PROGRESS_FILE)?;
+    let pending = progress::pending_part2("src/bin");
+    print!("{}", progress::render_calendar(&progress, &pending));
+    let solves = progress::render_solves(&progress);
+    if !solves.is_empty() {
+        println!();
+        print!("{solves}");
+    }
+    Ok(())
+}
+
+fn record(args: &[String]) -> Result<()> {
+    let [day, part, answer] = args else {
+        bail!("Usage: aoc record <day> <part> <answer>");
+    };
+    let day = parse_day(day)?;
+    let part: u8 = part.parse().context("Invalid part")?;
+    if !(1..=2).contains(&part) {
+        bail!("Part must be 1 or 2");
+    }
+    let Ok(answer) = answer.parse::<Answer>();
+    let mut progress = Progress::load(PROGRESS_FILE)?;
+    if progress.record(day, part, &answer) {
+        progress.save(PROGRESS_FILE)?;
+        println!("Recorded day {day:02} part {part}: {answer}");
+    } else if progress.check(day, part, &answer) == Some(true) {
+        println!("Day {day:02} part {part} already solved with {answer}");
+    } else {
+        let solve = progress.get(day, part).unwrap();
+        println!(
+            "Day {day:02} part {part} was solved with {}, not {answer}",
+            solve.answer
+        );
+    }
+    Ok(())
+}
+
+fn leaderboard(args: &[String]) -> Result<()> {
+    let [path] = args else {
+        bail!("Usage: aoc leaderboard <file.json>");
+    };
+    let board = Leaderboard::load(path)?;
+    println!(
+        "Private leaderboard {} (owner {})",
+        board.event, board.owner_id
+    );
+    println!();
+    print!("{}", board.render_ranking());
+    println!();
+    print!("{}", board.render_gaps());
+    println!();
+    print!("{}", board.render_histogram());
+    Ok(())
+}
+
+fn gen_input(args: &[String]) -> Result<()> {
+    let (positional, flags) = split_flags(args)?;
+    let [day] = positional[..] else {
+        bail!("Usage: aoc gen <day> [--size N] [--seed S] [--out FILE]");
+    };
+    let day = parse_day(day)?;
+    let generator = generator(day).ok_or_else(|| anyhow!("No generator for day {day:02}"))?;
+    let size: usize = flag(&flags, "size")?.unwrap_or(100);
+    let seed: u64 = match flag(&flags, "seed")? {
+        Some(seed) => seed,
+        None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+    };
+    let out = flags.get("out").map_or_else(
+        || format!("input/{day:02}-size{size}-seed{seed}.txt"),
+        ToString::to_string,
+    );
+    let text = generator.generate(&mut Rng::new(seed), size);
+    fs::write(&out, text).with_context(|| format!("Writing {out}"))?;
+    println!("Wrote day {day:02} input (size {size}, seed {seed}) to {out}");
+    Ok(())
+}
+
+fn dominators(args: &[String]) -> Result<()> {
+    let (positional, flags) = split_flags(args)?;
+    let graph = load_graph(
+        &positional,
+        "Usage: aoc dominators [file] [--from A] [--to B] [--through NODE]",
+    )?;
+    let from = graph.id(flags.get("from").unwrap_or(&"svr"))?;
+    let to = graph.id(flags.get("to").unwrap_or(&"out"))?;
+    let (from_name, to_name) = (graph.name(from), graph.name(to));
+
+    let total: u128 = count_paths(&graph, from, to)?;
+    if total == 0 {
+        println!("No paths from {from_name} to {to_name}");
+        return Ok(());
+    }
+    let names: Vec<&str> = must_pass(&graph, from, to)
+        .into_iter()
+        .map(|id| graph.name(id))
+        .collect();
+    println!("{total} paths from {from_name} to {to_name}, all through:");
+    println!("  {}", names.join(" -> "));
+
+    if let Some(&through) = flags.get("through") {
+        let node = graph.id(through)?;
+        let before: u128 = count_paths(&graph, from, node)?;
+        let after: u128 = count_paths(&graph, node, to)?;
+        let via = before
+            .checked_mul(after)
+            .context("Path count overflows u128")?;
+        #[allow(clippy::cast_precision_loss)]
+        let percent = via as f64 / total as f64 * 100.0;
+        println!("{via} of them ({percent:.2}%) go through {through}");
+    }
+    Ok(())
+}
+
+fn disjoint(args: &[String]) -> Result<()> {
+    let (positional, flags) = split_flags(args)?;
+    let graph = load_graph(
+        &positional,
+        "Usage: aoc disjoint [file] [--from A] [--to B]",
+    )?;
+    let from = graph.id(flags.get("from").unwrap_or(&"you"))?;
+    let to = graph.id(flags.get("to").unwrap_or(&"out"))?;
+    let (from_name, to_name) = (graph.name(from), graph.name(to));
+
+    let edges = edge_disjoint_paths(&graph, from, to)?;
+    println!(
+        "{} edge-disjoint paths from {from_name} to {to_name}",
+        edges.paths
+    );
+    let nodes = node_disjoint_paths(&graph, from, to)?;
+    println!(
+        "{} node-disjoint paths from {from_name} to {to_name}",
+        nodes.paths
+    );
+    if nodes.paths == 0 {
+        println!("{from_name} and {to_name} are already disconnected");
+    } else if !nodes.separates() {
+        println!(
+            "{from_name} has an edge straight to {to_name}, so removing devices cannot separate them"
+        );
+    } else {
+        let names: Vec<&str> = nodes.cut.iter().map(|&id| graph.name(id)).collect();
+        println!("Removing {{{}}} disconnects them", names.join(", "));
+    }
+    Ok(())
+}
+
+const DOT_USAGE: &str =
+    "Usage: aoc dot [file] [--day 11] [--from A] [--to B] [--highlight A,B,..] [--out FILE]
+       aoc dot [file] --day 08 [--pairs N] [--out FILE]";
+
+fn dot(args: &[String]) -> Result<()> {
+    let (positional, flags) = split_flags(args)?;
+    let day = flags.get("day").map_or(Ok(11), |day| parse_day(day))?;
+    let dot = match day {
+        8 => circuits_dot(&positional, &flags)?,
+        11 => device_dot(&positional, &flags)?,
+        _ => bail!("aoc dot draws days 08 and 11, not {day:02}\n{DOT_USAGE}"),
+    };
+    match flags.get("out") {
+        Some(out) => {
+            fs::write(out, dot).with_context(|| format!("Writing {out}"))?;
+            println!("Wrote {out}");
+        }
+        None => print!("{dot}"),
+    }
+    Ok(())
+}
+
+/// Day 11's devices, with edges labelled by path counts when the paths can be counted.
+fn device_dot(positional: &[&str], flags: &HashMap<&str, &str>) -> Result<String> {
+    let graph = load_graph(positional, DOT_USAGE)?;
+    let from = graph.id(flags.get("from").unwrap_or(&"svr"))?;
+    let to = graph.id(flags.get("to").unwrap_or(&"out"))?;
+    // Names asked for must exist; the defaults are only drawn where they do.
+    let highlight = match flags.get("highlight") {
+        Some(names) => names
+            .split(',')
+            .map(|name| graph.id(name))
+            .collect::<Result<Vec<_>>>()?,
+        None => ["svr", "dac", "fft", "out"]
+            .into_iter()
+            .filter_map(|name| graph.id(name).ok())
+            .collect(),
+    };
+    let dot = Dot::new(&graph).highlight(highlight);
+    // A cycle is exactly when looking at the graph helps, so draw it unlabelled.
+    let dot = match dot.clone().path_counts(from, to) {
+        Ok(labelled) => labelled,
+        Err(err) => {
+            eprintln!("Drawing without path counts: {err}");
+            dot
+        }
+    };
+    Ok(dot.to_string())
+}
+
+/// Day 08's junction boxes joined by the `--pairs` closest connections,
+/// each circuit filled in its own colour.
+fn circuits_dot(positional: &[&str], flags: &HashMap<&str, &str>) -> Result<String> {
+    let path = input_path(positional, "input/08.txt", DOT_USAGE)?;
+    let text = fs::read_to_string(path).with_context(|| format!("Reading {path}"))?;
+    let coords = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<Point3>>>()?;
+    let pairs: usize = flag(flags, "pairs")?.unwrap_or(1000);
+    let (graph, mut sets) = circuits(&coords, pairs)?;
+    Ok(Dot::new(&graph)
+        .name("day 08")
+        .undirected()
+        .components(&mut sets)
+        .to_string())
+}
+
+/// Parses a day 11 style graph, reading `input/11.txt` when no file is given.
+fn load_graph(positional: &[&str], usage: &str) -> Result<Graph> {
+    let path = input_path(positional, "input/11.txt", usage)?;
+    let text = fs::read_to_string(path).with_context(|| format!("Reading {path}"))?;
+    Graph::parse(&text)
+}
+
+/// The one optional file argument, or `default`.
+fn input_path<'a>(positional: &[&'a str], default: &'a str, usage: &str) -> Result<&'a str> {
+    match positional {
+        [] => Ok(default),
+        [path] => Ok(path),
+        _ => bail!("{usage}"),
+    }
+}
+
+/// Splits `--name value` pairs from positional arguments.
+fn split_flags(args: &[String]) -> Result<(Vec<&str>, HashMap<&str, &str>)> {
+    let mut positional = Vec::new();
+    let mut flags = HashMap::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if let Some(name) = arg.strip_prefix("--") {
+            let value = iter
+                .next()
+                .ok_or_else(|| anyhow!("Missing value for --{name}"))?;
+            flags.insert(name, value.as_str());
+        } else {
+            positional.push(arg.as_str());
+        }
+    }
+    Ok((positional, flags))
+}
+
+fn flag<T>(flags: &HashMap<&str, &str>, name: &str) -> Result<Option<T>>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    flags
+        .get(name)
+        .map(|v| v.parse().with_context(|| format!("Invalid --{name}: {v}")))
+        .transpose()
+}
+
+fn parse_day(day: &str) -> Result<u8> {
+    let day: u8 = day.parse().with_context(|| format!("Invalid day: {day}"))?;
+    if !(1..=DAYS).contains(&day) {
+        bail!("Day must be between 1 and {DAYS}");
+    }
+    Ok(day)
+}
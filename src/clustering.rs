@@ -0,0 +1,161 @@
+//! Single-linkage clustering: Kruskal's algorithm as a stream of merges.
+
+use crate::disjoint_set::DisjointSet;
+use std::marker::PhantomData;
+
+/// `(weight, a, b)`: an undirected edge between elements `a` and `b`.
+pub type Edge<W> = (W, usize, usize);
+
+/// Two clusters joined by an edge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Merge<W> {
+    pub weight: W,
+    pub a: usize,
+    pub b: usize,
+    /// Position of the edge in weight order, counting edges that merged nothing.
+    pub rank: usize,
+    /// Sizes of the clusters containing `a` and `b` just before the merge.
+    pub sizes: (usize, usize),
+    /// Clusters left after the merge.
+    pub components: usize,
+}
+
+/// Processes edges lightest first, yielding each one that joins two clusters.
+///
+/// Ties keep the order the edges were given in.
+pub struct Clustering<W, I = std::vec::IntoIter<Edge<W>>> {
+    edges: I,
+    next: usize,
+    sets: DisjointSet,
+    weight: PhantomData<W>,
+}
+
+impl<W: Ord + Copy> Clustering<W> {
+    /// Clusters `n` elements, each starting on its own.
+    pub fn new(n: usize, edges: impl IntoIterator<Item = Edge<W>>) -> Self {
+        let mut edges: Vec<Edge<W>> = edges.into_iter().collect();
+        edges.sort_by_key(|e| e.0);
+        Self::from_sorted(n, edges)
+    }
+}
+
+impl<W: Ord + Copy, I: Iterator<Item = Edge<W>>> Clustering<W, I> {
+    /// Like [`Clustering::new`] for edges that already come lightest first, so
+    /// they can be produced lazily.
+    pub fn from_sorted(n: usize, edges: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            edges: edges.into_iter(),
+            next: 0,
+            sets: DisjointSet::new(n),
+            weight: PhantomData,
+        }
+    }
+
+    /// Number of edges processed so far.
+    pub fn edges_seen(&self) -> usize {
+        self.next
+    }
+
+    /// Processes edges until `count` of them have been seen in total, returning the merges.
+    pub fn take_edges(&mut self, count: usize) -> Vec<Merge<W>> {
+        let mut merges = Vec::new();
+        while self.next < count {
+            match self.step() {
+                Some(merge) => merges.extend(merge),
+                None => break,
+            }
+        }
+        merges
+    }
+
+    /// The clusters as they stand.
+    pub fn sets(&self) -> &DisjointSet {
+        &self.sets
+    }
+
+    /// Processes the next edge: `None` once they run out, and then whether it merged.
+    fn step(&mut self) -> Option<Option<Merge<W>>> {
+        let (weight, a, b) = self.edges.next()?;
+        let rank = self.next;
+        self.next += 1;
+        let sizes = (self.sets.size_of(a), self.sets.size_of(b));
+        Some(self.sets.union(a, b).then(|| Merge {
+            weight,
+            a,
+            b,
+            rank,
+            sizes,
+            components: self.sets.component_count(),
+        }))
+    }
+}
+
+impl<W: Ord + Copy, I: Iterator<Item = Edge<W>>> Iterator for Clustering<W, I> {
+    type Item = Merge<W>;
+
+    fn next(&mut self) -> Option<Merge<W>> {
+        while self.sets.component_count() > 1 {
+            if let Some(merge) = self.step()? {
+                return Some(merge);
+            }
+        }
+        None
+    }
+}
+
+/// The edges of a minimum spanning tree, or forest if the graph isn't connected.
+pub fn minimum_spanning_tree<W: Ord + Copy>(
+    n: usize,
+    edges: impl IntoIterator<Item = Edge<W>>,
+) -> Vec<Merge<W>> {
+    Clustering::new(n, edges).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 - 1 - 2 in a triangle, plus 3 - 4 off to the side.
+    const EDGES: [Edge<u32>; 5] = [(3, 0, 2), (1, 0, 1), (2, 1, 2), (5, 2, 3), (1, 3, 4)];
+
+    #[test]
+    fn merge_stream() {
+        let merges: Vec<_> = Clustering::new(5, EDGES).collect();
+        let joined: Vec<_> = merges.iter().map(|m| (m.a, m.b, m.rank)).collect();
+        assert_eq!(joined, [(0, 1, 0), (3, 4, 1), (1, 2, 2), (2, 3, 4)]);
+        assert_eq!(merges[3].sizes, (3, 2));
+        assert_eq!(merges.last().unwrap().components, 1);
+        let total: u32 = minimum_spanning_tree(5, EDGES)
+            .iter()
+            .map(|m| m.weight)
+            .sum();
+        assert_eq!(total, 9);
+    }
+
+    #[test]
+    fn first_edges_only() {
+        let mut clustering = Clustering::new(5, EDGES);
+        assert_eq!(clustering.take_edges(4).len(), 3);
+        assert_eq!(clustering.edges_seen(), 4);
+        assert_eq!(clustering.sets().component_sizes(), [3, 2]);
+        assert_eq!(clustering.next().map(|m| m.weight), Some(5));
+        assert_eq!(clustering.next(), None);
+    }
+
+    #[test]
+    fn lazy_sorted_edges() {
+        // A path 0 - 1 - 2 - ..., then an endless supply of redundant edges.
+        let edges = (0..).map(|i| (i, i % 10, i % 10 + 1));
+        let mut clustering = Clustering::from_sorted(11, edges);
+        let last = clustering.by_ref().last().unwrap();
+        assert_eq!((last.rank, last.components), (9, 1));
+        assert_eq!(clustering.edges_seen(), 10);
+    }
+
+    #[test]
+    fn disconnected_gives_a_forest() {
+        let forest = minimum_spanning_tree(4, [(1, 0, 1), (1, 2, 3)]);
+        assert_eq!(forest.len(), 2);
+        assert_eq!(forest[1].components, 2);
+    }
+}
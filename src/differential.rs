@@ -0,0 +1,293 @@
+//! Differential testing: run several implementations of the same part on
+//! generated inputs and shrink any disagreement to a minimal failing input.
+
+use crate::generate::Generate;
+use crate::rng::Rng;
+use anyhow::{Result, bail};
+use std::fmt::{self, Debug, Display};
+use std::ops::Range;
+
+type Implementation<'a, T> = Box<dyn Fn(&str) -> Result<T> + 'a>;
+
+pub struct Differential<'a, T> {
+    implementations: Vec<(&'a str, Implementation<'a, T>)>,
+}
+
+/// What one implementation made of an input: its answer or its error message.
+pub type Output<T> = std::result::Result<T, String>;
+
+/// Implementations that disagree on an input, after shrinking it.
+#[derive(Debug)]
+pub struct Mismatch<T> {
+    pub seed: u64,
+    pub input: String,
+    pub outputs: Vec<(String, Output<T>)>,
+}
+
+impl<T: Debug> Display for Mismatch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Implementations disagree (seed {}):", self.seed)?;
+        for (name, output) in &self.outputs {
+            match output {
+                Ok(answer) => writeln!(f, "  {name}: {answer:?}")?,
+                Err(error) => writeln!(f, "  {name}: error: {error}")?,
+            }
+        }
+        write!(f, "Minimal input:\n{}", self.input)
+    }
+}
+
+impl<T> Default for Differential<'_, T> {
+    fn default() -> Self {
+        Self {
+            implementations: Vec::new(),
+        }
+    }
+}
+
+impl<'a, T: PartialEq + Debug> Differential<'a, T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(mut self, name: &'a str, f: impl Fn(&str) -> Result<T> + 'a) -> Self {
+        self.implementations.push((name, Box::new(f)));
+        self
+    }
+
+    /// Outputs of every implementation if they disagree on `input`.
+    ///
+    /// An error disagrees with any answer. Inputs that every implementation
+    /// rejects are skipped, so shrinking never wanders off into inputs nobody
+    /// accepts; the error messages themselves are not compared.
+    pub fn disagreement(&self, input: &str) -> Option<Vec<(String, Output<T>)>> {
+        let outputs: Vec<(String, Output<T>)> = self
+            .implementations
+            .iter()
+            .map(|(name, f)| (name.to_string(), f(input).map_err(|e| format!("{e:#}"))))
+            .collect();
+        if outputs.iter().all(|(_, output)| output.is_err()) {
+            return None;
+        }
+        let first = &outputs[0].1;
+        if outputs.iter().all(|(_, output)| output == first) {
+            None
+        } else {
+            Some(outputs)
+        }
+    }
+
+    /// Runs every implementation on one generated input per seed and returns
+    /// the first disagreement, shrunk.
+    pub fn find_mismatch(
+        &self,
+        generator: &dyn Generate,
+        size: usize,
+        seeds: Range<u64>,
+    ) -> Option<Mismatch<T>> {
+        for seed in seeds {
+            let input = generator.generate(&mut Rng::new(seed), size);
+            if self.disagreement(&input).is_some() {
+                let input = self.shrink(input);
+                let outputs = self.disagreement(&input).unwrap();
+                return Some(Mismatch {
+                    seed,
+                    input,
+                    outputs,
+                });
+            }
+        }
+        None
+    }
+
+    /// Like [`Self::find_mismatch`], but as an error to bail out with.
+    pub fn check(&self, generator: &dyn Generate, size: usize, seeds: Range<u64>) -> Result<()> {
+        if let Some(mismatch) = self.find_mismatch(generator, size, seeds) {
+            bail!("{mismatch}");
+        }
+        Ok(())
+    }
+
+    /// Greedily simplifies a failing input until no single step keeps it failing:
+    /// drop chunks of lines, drop chunks of comma-separated items within a line,
+    /// then make numbers smaller.
+    pub fn shrink(&self, mut input: String) -> String {
+        let fails = |candidate: &str| self.disagreement(candidate).is_some();
+        loop {
+            let before = input.clone();
+            input = shrink_items(&input, '\n', &fails);
+            for i in 0..input.split('\n').count() {
+                let current: Vec<&str> = input.split('\n').collect();
+                let rebuild = |line: &str| {
+                    let mut parts = current.clone();
+                    parts[i] = line;
+                    parts.join("\n")
+                };
+                let shrunk = shrink_items(current[i], ',', &|line| fails(&rebuild(line)));
+                input = rebuild(&shrunk);
+            }
+            input = shrink_numbers(&input, &fails);
+            if input == before {
+                return input;
+            }
+        }
+    }
+}
+
+/// Delta debugging over `sep`-separated items: removes ever smaller chunks while `fails` holds.
+fn shrink_items(text: &str, sep: char, fails: &dyn Fn(&str) -> bool) -> String {
+    let trailing = sep == '\n' && text.ends_with('\n');
+    let body = if trailing {
+        &text[..text.len() - 1]
+    } else {
+        text
+    };
+    let mut items: Vec<&str> = body.split(sep).collect();
+    let join = |items: &[&str]| {
+        let mut s = items.join(&sep.to_string());
+        if trailing {
+            s.push('\n');
+        }
+        s
+    };
+    let mut chunk = items.len() / 2;
+    while chunk > 0 {
+        let mut start = 0;
+        while start < items.len() && items.len() > 1 {
+            let end = (start + chunk).min(items.len());
+            let candidate: Vec<&str> = [&items[..start], &items[end..]].concat();
+            if !candidate.is_empty() && fails(&join(&candidate)) {
+                items = candidate;
+            } else {
+                start += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+    join(&items)
+}
+
+/// Replaces each number with the smallest of a few simpler values that still fails.
+fn shrink_numbers(text: &str, fails: &dyn Fn(&str) -> bool) -> String {
+    let mut text = text.to_string();
+    let mut pos = 0;
+    while let Some(offset) = text[pos..].find(|c: char| c.is_ascii_digit()) {
+        let start = pos + offset;
+        let end = text[start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(text.len(), |e| start + e);
+        if let Ok(n) = text[start..end].parse::<u64>() {
+            for smaller in [0, 1, n / 10, n / 2, n.saturating_sub(1)] {
+                if smaller >= n {
+                    continue;
+                }
+                let candidate = format!("{}{smaller}{}", &text[..start], &text[end..]);
+                if fails(&candidate) {
+                    text = candidate;
+                    break;
+                }
+            }
+        }
+        pos = start
+            + text[start..]
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(text.len() - start);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lines;
+
+    impl Generate for Lines {
+        fn generate(&self, rng: &mut Rng, size: usize) -> String {
+            (0..size)
+                .map(|_| format!("{}\n", rng.range(0..=1000)))
+                .collect()
+        }
+    }
+
+    fn sum(input: &str) -> Result<u64> {
+        Ok(input
+            .lines()
+            .map(|l| l.parse::<u64>())
+            .sum::<Result<_, _>>()?)
+    }
+
+    #[test]
+    fn agreeing_implementations_pass() {
+        let diff = Differential::new()
+            .register("sum", sum)
+            .register("fold", |s| {
+                s.lines().try_fold(0, |acc, l| Ok(acc + l.parse::<u64>()?))
+            });
+        assert!(diff.check(&Lines, 30, 0..20).is_ok());
+    }
+
+    #[test]
+    fn shrinks_to_single_failing_line() {
+        // "Optimized" version that forgets numbers over 700.
+        let diff = Differential::new()
+            .register("sum", sum)
+            .register("buggy", |s| {
+                Ok(s.lines()
+                    .map(|l| l.parse::<u64>().unwrap())
+                    .filter(|&n| n <= 700)
+                    .sum())
+            });
+        let mismatch = diff.find_mismatch(&Lines, 30, 0..20).unwrap();
+        assert_eq!(mismatch.input, "701\n");
+        assert_eq!(
+            mismatch.outputs,
+            [("sum".to_string(), Ok(701)), ("buggy".to_string(), Ok(0))]
+        );
+    }
+
+    #[test]
+    fn errors_disagree_with_answers() {
+        // Rejects numbers over 900 that the reference happily adds up.
+        let diff = Differential::new()
+            .register("sum", sum)
+            .register("strict", |s| {
+                s.lines().try_fold(0, |acc, l| {
+                    let n: u64 = l.parse()?;
+                    if n > 900 {
+                        bail!("{n} is too big");
+                    }
+                    Ok(acc + n)
+                })
+            });
+        let mismatch = diff.find_mismatch(&Lines, 30, 0..20).unwrap();
+        assert_eq!(mismatch.input, "901\n");
+        assert_eq!(
+            mismatch.outputs,
+            [
+                ("sum".to_string(), Ok(901)),
+                ("strict".to_string(), Err("901 is too big".to_string()))
+            ]
+        );
+        assert!(
+            mismatch
+                .to_string()
+                .contains("strict: error: 901 is too big")
+        );
+
+        // Inputs that every implementation rejects are not mismatches.
+        assert_eq!(diff.disagreement("x\n"), None);
+    }
+
+    #[test]
+    fn shrinks_comma_separated_items() {
+        let count = |s: &str| -> Result<usize> { Ok(s.trim().split(',').count()) };
+        let diff = Differential::new()
+            .register("count", count)
+            .register("capped", move |s| Ok(count(s)?.min(3)));
+        let mismatch = diff
+            .find_mismatch(&crate::generate::Day02, 10, 0..5)
+            .unwrap();
+        assert_eq!(mismatch.input, "0-0,0-0,0-0,0-0\n");
+    }
+}
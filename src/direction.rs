@@ -0,0 +1,228 @@
+//! Compass directions on a grid whose `y` grows downwards.
+
+use crate::point::Point2;
+use anyhow::{Result, bail};
+use std::str::FromStr;
+
+/// One of the four orthogonal directions, clockwise from `Up`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// One of the eight orthogonal and diagonal directions, clockwise from `Up`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    fn from_index(i: usize) -> Self {
+        Self::ALL[i % 4]
+    }
+
+    /// A quarter turn counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self as usize + 3)
+    }
+
+    /// A quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self as usize + 1)
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::from_index(self as usize + 2)
+    }
+
+    /// `(dx, dy)` of one step, as used by [`Grid::offset`](crate::grid::Grid::offset).
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+
+    pub fn delta(self) -> Point2 {
+        let (dx, dy) = self.offset();
+        Point2::new(dx as i64, dy as i64)
+    }
+
+    /// Parses `U`/`R`/`D`/`L`, `N`/`E`/`S`/`W` (either case) or an arrow `^`/`>`/`v`/`<`.
+    pub fn from_char(c: char) -> Result<Self> {
+        Ok(match c {
+            'U' | 'u' | 'N' | 'n' | '^' => Direction::Up,
+            'R' | 'r' | 'E' | 'e' | '>' => Direction::Right,
+            'D' | 'd' | 'S' | 's' | 'v' => Direction::Down,
+            'L' | 'l' | 'W' | 'w' | '<' => Direction::Left,
+            _ => bail!("Invalid direction {c:?}"),
+        })
+    }
+}
+
+impl FromStr for Direction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::from_char(c),
+            _ => bail!("Invalid direction {s:?}"),
+        }
+    }
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    fn from_index(i: usize) -> Self {
+        Self::ALL[i % 8]
+    }
+
+    /// Turned clockwise by `steps` eighths of a turn.
+    pub fn rotate(self, steps: usize) -> Self {
+        Self::from_index(self as usize + steps % 8)
+    }
+
+    /// A quarter turn counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    /// A quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// `(dx, dy)` of one step, as used by [`Grid::offset`](crate::grid::Grid::offset).
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction8::Up => (0, -1),
+            Direction8::UpRight => (1, -1),
+            Direction8::Right => (1, 0),
+            Direction8::DownRight => (1, 1),
+            Direction8::Down => (0, 1),
+            Direction8::DownLeft => (-1, 1),
+            Direction8::Left => (-1, 0),
+            Direction8::UpLeft => (-1, -1),
+        }
+    }
+
+    pub fn delta(self) -> Point2 {
+        let (dx, dy) = self.offset();
+        Point2::new(dx as i64, dy as i64)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(d: Direction) -> Self {
+        Self::from_index(d as usize * 2)
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = anyhow::Error;
+
+    /// A single [`Direction`] character, or two of them for a diagonal: `NE`, `DL`, `^>`...
+    fn from_str(s: &str) -> Result<Self> {
+        let dirs = s
+            .chars()
+            .map(Direction::from_char)
+            .collect::<Result<Vec<_>>>()?;
+        let vertical = |d: &Direction| matches!(d, Direction::Up | Direction::Down);
+        Ok(match dirs[..] {
+            [d] => d.into(),
+            [a, b] if vertical(&a) != vertical(&b) => {
+                let (v, h) = if vertical(&a) { (a, b) } else { (b, a) };
+                match (v, h) {
+                    (Direction::Up, Direction::Right) => Direction8::UpRight,
+                    (Direction::Down, Direction::Right) => Direction8::DownRight,
+                    (Direction::Down, _) => Direction8::DownLeft,
+                    _ => Direction8::UpLeft,
+                }
+            }
+            _ => bail!("Invalid direction {s:?}"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_right(), d.reverse());
+            assert_eq!(d.delta() + d.reverse().delta(), Point2::default());
+            assert_eq!(Direction8::from(d).turn_right(), d.turn_right().into());
+        }
+        assert_eq!(Direction8::UpRight.turn_right(), Direction8::DownRight);
+        assert_eq!(Direction8::UpLeft.reverse(), Direction8::DownRight);
+        assert_eq!(Direction8::Up.rotate(9), Direction8::UpRight);
+        assert_eq!(Direction8::DownLeft.delta(), Point2::new(-1, 1));
+        assert_eq!(
+            Direction8::ALL.iter().filter(|d| d.is_diagonal()).count(),
+            4
+        );
+    }
+
+    #[test]
+    fn parsing() {
+        for (s, d) in [
+            ("U", Direction::Up),
+            ("e", Direction::Right),
+            ("v", Direction::Down),
+        ] {
+            assert_eq!(s.parse::<Direction>().unwrap(), d);
+        }
+        assert_eq!("<".parse::<Direction>().unwrap(), Direction::Left);
+        assert!("X".parse::<Direction>().is_err());
+        assert!("UR".parse::<Direction>().is_err());
+        assert_eq!("NE".parse::<Direction8>().unwrap(), Direction8::UpRight);
+        assert_eq!("LD".parse::<Direction8>().unwrap(), Direction8::DownLeft);
+        assert_eq!("^<".parse::<Direction8>().unwrap(), Direction8::UpLeft);
+        assert_eq!("S".parse::<Direction8>().unwrap(), Direction8::Down);
+        assert!("NS".parse::<Direction8>().is_err());
+    }
+}
@@ -0,0 +1,270 @@
+//! Random puzzle inputs for stress testing, one generator per day.
+
+use crate::rng::Rng;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+pub trait Generate {
+    /// A random valid input with roughly `size` items (lines, ranges, points, ...).
+    fn generate(&self, rng: &mut Rng, size: usize) -> String;
+}
+
+/// The generator for `day`, if it has one.
+pub fn generator(day: u8) -> Option<&'static dyn Generate> {
+    match day {
+        1 => Some(&Day01),
+        2 => Some(&Day02),
+        3 => Some(&Day03),
+        8 => Some(&Day08),
+        9 => Some(&Day09),
+        10 => Some(&Day10),
+        11 => Some(&Day11),
+        _ => None,
+    }
+}
+
+/// Dial rotations: `size` lines of `L` or `R` followed by a distance.
+pub struct Day01;
+
+impl Generate for Day01 {
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let mut out = String::new();
+        for _ in 0..size {
+            let dir = if rng.chance(1, 2) { 'L' } else { 'R' };
+            writeln!(out, "{dir}{}", rng.range(1..=999)).unwrap();
+        }
+        out
+    }
+}
+
+/// ID ranges: `size` comma-separated `start-end` pairs on a single line.
+pub struct Day02;
+
+impl Generate for Day02 {
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let ranges: Vec<String> = (0..size)
+            .map(|_| {
+                let digits = rng.range(1..=10) as u32;
+                let start = rng.range(10_i64.pow(digits - 1)..=10_i64.pow(digits) - 1);
+                let end = start + rng.range(0..=999);
+                format!("{start}-{end}")
+            })
+            .collect();
+        ranges.join(",") + "\n"
+    }
+}
+
+/// Battery banks: `size` lines of 12 to 16 digits from 1 to 9.
+///
+/// Kept short so brute-force reference implementations stay cheap.
+pub struct Day03;
+
+impl Generate for Day03 {
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let mut out = String::new();
+        for _ in 0..size {
+            for _ in 0..rng.range(12..=16) {
+                out.push(char::from(b'0' + rng.range(1..=9) as u8));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Junction boxes: `size` distinct `x,y,z` points in a 100000-wide cube.
+pub struct Day08;
+
+impl Generate for Day08 {
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let mut seen = HashSet::new();
+        let mut out = String::new();
+        while seen.len() < size {
+            let p = (
+                rng.range(0..=99_999),
+                rng.range(0..=99_999),
+                rng.range(0..=99_999),
+            );
+            if seen.insert(p) {
+                writeln!(out, "{},{},{}", p.0, p.1, p.2).unwrap();
+            }
+        }
+        out
+    }
+}
+
+/// Red tiles: the corners of a rectilinear polygon, listed in order.
+///
+/// The polygon is a histogram: a flat bottom edge and a skyline of columns
+/// with distinct neighbouring heights, so it has `size` rounded to an even
+/// number of corners (at least 4).
+pub struct Day09;
+
+impl Generate for Day09 {
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let columns = size.max(4) / 2 - 1;
+        let mut x = rng.range(0..=1000);
+        let mut out = String::new();
+        writeln!(out, "{x},0").unwrap();
+        let mut height = 0;
+        for _ in 0..columns {
+            let mut next = rng.range(1..=99_999);
+            while next == height {
+                next = rng.range(1..=99_999);
+            }
+            height = next;
+            writeln!(out, "{x},{height}").unwrap();
+            x += rng.range(1..=5000);
+            writeln!(out, "{x},{height}").unwrap();
+        }
+        writeln!(out, "{x},0").unwrap();
+        out
+    }
+}
+
+/// Machines: an indicator light diagram, wiring buttons and joltage targets per line.
+///
+/// Both targets are built from random button presses, so every machine is solvable.
+pub struct Day10;
+
+impl Generate for Day10 {
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let mut out = String::new();
+        for _ in 0..size {
+            let lights = rng.index(8) + 3;
+            let buttons: Vec<Vec<usize>> = (0..rng.index(lights + 2) + 2)
+                .map(|_| {
+                    let mut wires: Vec<usize> = (0..lights).filter(|_| rng.chance(2, 5)).collect();
+                    if wires.is_empty() {
+                        wires.push(rng.index(lights));
+                    }
+                    wires
+                })
+                .collect();
+            let mut lit = vec![false; lights];
+            let mut joltage = vec![0; lights];
+            for wires in &buttons {
+                let presses = rng.range(0..=10);
+                for &w in wires {
+                    lit[w] ^= presses % 2 == 1;
+                    joltage[w] += presses;
+                }
+            }
+            let diagram: String = lit.iter().map(|&on| if on { '#' } else { '.' }).collect();
+            write!(out, "[{diagram}]").unwrap();
+            for wires in &buttons {
+                let wires: Vec<String> = wires.iter().map(ToString::to_string).collect();
+                write!(out, " ({})", wires.join(",")).unwrap();
+            }
+            let joltage: Vec<String> = joltage.iter().map(ToString::to_string).collect();
+            writeln!(out, " {{{}}}", joltage.join(",")).unwrap();
+        }
+        out
+    }
+}
+
+/// Device graph: a DAG of `size` nodes in `src: dst dst` format.
+///
+/// Contains `svr` and `you` near the start, `dac` and `fft` somewhere in the
+/// middle and `out` as the only sink; every edge points to a later node.
+pub struct Day11;
+
+impl Generate for Day11 {
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        const RESERVED: [&str; 5] = ["svr", "you", "dac", "fft", "out"];
+        let size = size.max(RESERVED.len());
+        let mut used: HashSet<String> = RESERVED.iter().map(ToString::to_string).collect();
+        let mut names = Vec::with_capacity(size);
+        while names.len() < size - RESERVED.len() {
+            let name: String = (0..3)
+                .map(|_| char::from(b'a' + rng.below(26) as u8))
+                .collect();
+            if used.insert(name.clone()) {
+                names.push(name);
+            }
+        }
+        names.insert(0, "svr".to_string());
+        names.insert(1, "you".to_string());
+        let middle = 2 + rng.index(size - 4);
+        names.insert(middle, "dac".to_string());
+        let middle = 2 + rng.index(size - 3);
+        names.insert(middle, "fft".to_string());
+        names.push("out".to_string());
+
+        let mut out = String::new();
+        for (i, name) in names.iter().enumerate().take(size - 1) {
+            let later = size - i - 1;
+            let mut targets: Vec<&str> = (0..rng.index(3.min(later)) + 1)
+                .map(|_| names[i + 1 + rng.index(later.min(8))].as_str())
+                .collect();
+            targets.sort_unstable();
+            targets.dedup();
+            writeln!(out, "{name}: {}", targets.join(" ")).unwrap();
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic() {
+        for day in 1..=12 {
+            if let Some(generator) = generator(day) {
+                let a = generator.generate(&mut Rng::new(5), 20);
+                let b = generator.generate(&mut Rng::new(5), 20);
+                assert_eq!(a, b, "day {day}");
+                assert!(!a.is_empty(), "day {day}");
+            }
+        }
+    }
+
+    #[test]
+    fn day09_is_rectilinear_loop() {
+        let text = Day09.generate(&mut Rng::new(3), 12);
+        let points: Vec<(i64, i64)> = text
+            .lines()
+            .map(|l| {
+                let (x, y) = l.split_once(',').unwrap();
+                (x.parse().unwrap(), y.parse().unwrap())
+            })
+            .collect();
+        assert_eq!(points.len(), 12);
+        for i in 0..points.len() {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            assert!((a.0 == b.0) != (a.1 == b.1), "{a:?} -> {b:?}");
+        }
+    }
+
+    #[test]
+    fn day10_fits_machine_format() {
+        let text = Day10.generate(&mut Rng::new(9), 10);
+        for line in text.lines() {
+            let lights = line[1..line.find(']').unwrap()].len();
+            let joltage = &line[line.find('{').unwrap() + 1..line.len() - 1];
+            assert_eq!(joltage.split(',').count(), lights);
+        }
+    }
+
+    #[test]
+    fn day11_is_dag_with_named_nodes() {
+        let text = Day11.generate(&mut Rng::new(11), 30);
+        let order: Vec<&str> = text.lines().map(|l| &l[..3]).collect();
+        assert_eq!(order.len(), 29);
+        for name in ["svr", "you", "dac", "fft"] {
+            assert!(order.contains(&name), "{name}");
+        }
+        for (i, line) in text.lines().enumerate() {
+            let (_, targets) = line.split_once(": ").unwrap();
+            for target in targets.split(' ') {
+                let pos = order
+                    .iter()
+                    .position(|&n| n == target)
+                    .unwrap_or(order.len());
+                assert!(pos > i, "{line}");
+            }
+        }
+    }
+}
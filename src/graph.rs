@@ -0,0 +1,353 @@
+//! Directed graphs with named nodes.
+//!
+//! Names are interned once, so algorithms work on dense [`NodeId`]s and plain
+//! `Vec`s instead of hashing strings.
+
+pub mod dominators;
+pub mod dot;
+pub mod flow;
+pub mod paths;
+pub mod scc;
+
+use anyhow::{Context, Result, bail};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Display};
+
+/// Index of a node in its [`Graph`], in the order nodes were first named.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(pub u32);
+
+impl NodeId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    successors: Vec<Vec<NodeId>>,
+    predecessors: Vec<Vec<NodeId>>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses lines of `name: successor successor ...`. Nodes that only appear
+    /// as successors are added too.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut graph = Self::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Some((source, successors)) = line.split_once(':') else {
+                bail!("Line {}: expected `name: successors`, got {line:?}", i + 1);
+            };
+            let source = source.trim();
+            if source.is_empty() {
+                bail!("Line {}: missing node name", i + 1);
+            }
+            let source = graph.node(source);
+            for successor in successors.split_whitespace() {
+                let successor = graph.node(successor);
+                graph.add_edge(source, successor);
+            }
+        }
+        Ok(graph)
+    }
+
+    /// The id for `name`, adding a node if there isn't one yet.
+    pub fn node(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = NodeId(u32::try_from(self.names.len()).expect("too many nodes"));
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.successors.push(Vec::new());
+        self.predecessors.push(Vec::new());
+        id
+    }
+
+    /// The id of an existing node.
+    pub fn id(&self, name: &str) -> Result<NodeId> {
+        self.ids
+            .get(name)
+            .copied()
+            .with_context(|| format!("No node named {name:?}"))
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.ids.contains_key(name)
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id.index()]
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) {
+        self.successors[from.index()].push(to);
+        self.predecessors[to.index()].push(from);
+    }
+
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.successors.iter().map(Vec::len).sum()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> + use<> {
+        (0..self.names.len() as u32).map(NodeId)
+    }
+
+    /// Every edge as `(from, to)`, grouped by `from`.
+    pub fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId)> + '_ {
+        self.nodes()
+            .flat_map(move |from| self.successors(from).iter().map(move |&to| (from, to)))
+    }
+
+    /// Targets of the edges leaving `id`, in the order they were added.
+    pub fn successors(&self, id: NodeId) -> &[NodeId] {
+        &self.successors[id.index()]
+    }
+
+    /// Sources of the edges entering `id`.
+    pub fn predecessors(&self, id: NodeId) -> &[NodeId] {
+        &self.predecessors[id.index()]
+    }
+
+    /// Nodes reachable from `from` (including itself), indexed by [`NodeId::index`].
+    pub fn reachable_from(&self, from: NodeId) -> Vec<bool> {
+        self.reachable_avoiding(from, &vec![false; self.len()])
+    }
+
+    /// Nodes from which `to` is reachable (including itself).
+    pub fn reaching(&self, to: NodeId) -> Vec<bool> {
+        self.reaching_avoiding(to, &vec![false; self.len()])
+    }
+
+    /// Like [`Graph::reachable_from`], without passing through nodes marked in `avoid`.
+    /// Nothing is reachable from an avoided node.
+    pub fn reachable_avoiding(&self, from: NodeId, avoid: &[bool]) -> Vec<bool> {
+        self.search(from, avoid, |id| self.successors(id))
+    }
+
+    /// Like [`Graph::reaching`], without passing through nodes marked in `avoid`.
+    pub fn reaching_avoiding(&self, to: NodeId, avoid: &[bool]) -> Vec<bool> {
+        self.search(to, avoid, |id| self.predecessors(id))
+    }
+
+    fn search<'a>(
+        &'a self,
+        start: NodeId,
+        avoid: &[bool],
+        next: impl Fn(NodeId) -> &'a [NodeId],
+    ) -> Vec<bool> {
+        let mut seen = vec![false; self.len()];
+        if avoid[start.index()] {
+            return seen;
+        }
+        seen[start.index()] = true;
+        let mut stack = vec![start];
+        while let Some(id) = stack.pop() {
+            for &n in next(id) {
+                if !seen[n.index()] && !avoid[n.index()] {
+                    seen[n.index()] = true;
+                    stack.push(n);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Every node ordered so that edges only point forwards. Fails on a cycle.
+    pub fn topological_order(&self) -> Result<Vec<NodeId>> {
+        self.topological_order_of(&vec![true; self.len()])
+    }
+
+    /// A topological order of the nodes marked in `include`, ignoring the rest.
+    pub fn topological_order_of(&self, include: &[bool]) -> Result<Vec<NodeId>> {
+        let mut in_degree = vec![0usize; self.len()];
+        for (from, to) in self.edges() {
+            if include[from.index()] && include[to.index()] {
+                in_degree[to.index()] += 1;
+            }
+        }
+        let mut queue: VecDeque<NodeId> = self
+            .nodes()
+            .filter(|id| include[id.index()] && in_degree[id.index()] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(queue.len());
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for &to in self.successors(id) {
+                if include[to.index()] {
+                    in_degree[to.index()] -= 1;
+                    if in_degree[to.index()] == 0 {
+                        queue.push_back(to);
+                    }
+                }
+            }
+        }
+        if order.len() < include.iter().filter(|&&i| i).count() {
+            let cycle =
+                scc::find_cycle(self, include).expect("Kahn's algorithm stalls only on a cycle");
+            bail!("Graph has a cycle: {}", self.format_cycle(&cycle));
+        }
+        Ok(order)
+    }
+
+    /// `a -> b -> c -> a` for the cycle `[a, b, c]`.
+    pub fn format_cycle(&self, cycle: &[NodeId]) -> String {
+        let names: Vec<&str> = cycle
+            .iter()
+            .chain(cycle.first())
+            .map(|&id| self.name(id))
+            .collect();
+        names.join(" -> ")
+    }
+
+    /// The same nodes with every edge turned around.
+    pub fn reversed(&self) -> Self {
+        Self {
+            names: self.names.clone(),
+            ids: self.ids.clone(),
+            successors: self.predecessors.clone(),
+            predecessors: self.successors.clone(),
+        }
+    }
+}
+
+/// Writes the graph back out in the format [`Graph::parse`] reads, skipping nodes without successors.
+impl Display for Graph {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for id in self.nodes() {
+            if self.successors(id).is_empty() {
+                continue;
+            }
+            write!(f, "{}:", self.name(id))?;
+            for &to in self.successors(id) {
+                write!(f, " {}", self.name(to))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fixtures shared by the tests of this module and its submodules.
+#[cfg(test)]
+mod test_support {
+    use super::{Graph, NodeId};
+
+    /// Day 11's first example, with paths from `you` to `out`.
+    pub const PART_1_EXAMPLE: &str = "\
+aaa: you hhh
+you: bbb ccc
+bbb: ddd eee
+ccc: ddd eee fff
+ddd: ggg
+eee: out
+fff: out
+ggg: out
+hhh: ccc fff iii
+iii: out
+";
+
+    /// Day 11's second example, with paths from `svr` through `dac` and `fft` to `out`.
+    pub const PART_2_EXAMPLE: &str = "\
+svr: aaa bbb
+aaa: fft
+fft: ccc
+bbb: tty
+tty: ccc
+ccc: ddd eee
+ddd: hub
+hub: fff
+eee: dac
+dac: fff
+fff: ggg hhh
+ggg: out
+hhh: out
+";
+
+    pub fn names(graph: &Graph, ids: &[NodeId]) -> Vec<String> {
+        ids.iter().map(|&id| graph.name(id).to_string()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "you: bbb ccc\nbbb: out\nccc: bbb out\n";
+
+    #[test]
+    fn parse_and_query() {
+        let graph = Graph::parse(TEXT).unwrap();
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.edge_count(), 5);
+        let you = graph.id("you").unwrap();
+        let bbb = graph.id("bbb").unwrap();
+        let out = graph.id("out").unwrap();
+        assert_eq!(you, NodeId(0));
+        assert_eq!(graph.name(out), "out");
+        assert_eq!(graph.successors(you).len(), 2);
+        assert!(graph.successors(out).is_empty());
+        let into_bbb: Vec<&str> = graph
+            .predecessors(bbb)
+            .iter()
+            .map(|&id| graph.name(id))
+            .collect();
+        assert_eq!(into_bbb, ["you", "ccc"]);
+        assert!(graph.id("svr").is_err());
+        assert_eq!(graph.to_string(), TEXT);
+        assert_eq!(graph.reversed().successors(bbb), graph.predecessors(bbb));
+    }
+
+    #[test]
+    fn ordering() {
+        let graph = Graph::parse(TEXT).unwrap();
+        let order: Vec<&str> = graph
+            .topological_order()
+            .unwrap()
+            .into_iter()
+            .map(|id| graph.name(id))
+            .collect();
+        assert_eq!(order, ["you", "ccc", "bbb", "out"]);
+        let ccc = graph.id("ccc").unwrap();
+        assert_eq!(graph.reachable_from(ccc), [false, true, true, true]);
+        assert_eq!(graph.reaching(ccc), [true, false, true, false]);
+
+        let cyclic = Graph::parse("a: b\nb: c\nc: b\nx: a\n").unwrap();
+        let err = cyclic.topological_order().unwrap_err();
+        assert_eq!(err.to_string(), "Graph has a cycle: b -> c -> b");
+        let a = cyclic.id("a").unwrap();
+        let only_x_a = cyclic.reaching(a);
+        assert_eq!(cyclic.topological_order_of(&only_x_a).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(Graph::parse("you bbb\n").is_err());
+        assert!(Graph::parse(": bbb\n").is_err());
+        assert!(Graph::parse("\n\nyou:\n").is_ok());
+    }
+}
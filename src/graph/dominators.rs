@@ -0,0 +1,178 @@
+//! Dominator trees, using the iterative algorithm of Cooper, Harvey and Kennedy.
+//!
+//! Node `d` dominates `n` if every path from the root to `n` goes through `d`.
+//! Post-dominators are the same thing on the reversed graph: every path from
+//! `n` to the exit goes through `d`.
+
+use super::{Graph, NodeId};
+
+#[derive(Clone, Debug)]
+pub struct DominatorTree {
+    root: NodeId,
+    /// Immediate dominator of each node; the root is its own, unreachable nodes have none.
+    idom: Vec<Option<NodeId>>,
+}
+
+impl DominatorTree {
+    /// Dominators of every node reachable from `root`.
+    pub fn new(graph: &Graph, root: NodeId) -> Self {
+        Self::build(graph, root, false)
+    }
+
+    /// Post-dominators of every node that can reach `exit`.
+    pub fn post_dominators(graph: &Graph, exit: NodeId) -> Self {
+        Self::build(graph, exit, true)
+    }
+
+    fn build(graph: &Graph, root: NodeId, backwards: bool) -> Self {
+        let forward = |id| {
+            if backwards {
+                graph.predecessors(id)
+            } else {
+                graph.successors(id)
+            }
+        };
+        let backward = |id| {
+            if backwards {
+                graph.successors(id)
+            } else {
+                graph.predecessors(id)
+            }
+        };
+
+        // Postorder numbers from an iterative depth-first search.
+        let mut postorder = Vec::new();
+        let mut number = vec![usize::MAX; graph.len()];
+        let mut visited = vec![false; graph.len()];
+        visited[root.index()] = true;
+        let mut stack = vec![(root, 0)];
+        while let Some(&mut (v, ref mut tried)) = stack.last_mut() {
+            if let Some(&w) = forward(v).get(*tried) {
+                *tried += 1;
+                if !visited[w.index()] {
+                    visited[w.index()] = true;
+                    stack.push((w, 0));
+                }
+            } else {
+                number[v.index()] = postorder.len();
+                postorder.push(v);
+                stack.pop();
+            }
+        }
+
+        let mut idom = vec![None; graph.len()];
+        idom[root.index()] = Some(root);
+        let intersect = |idom: &[Option<NodeId>], mut a: NodeId, mut b: NodeId| {
+            while a != b {
+                while number[a.index()] < number[b.index()] {
+                    a = idom[a.index()].unwrap();
+                }
+                while number[b.index()] < number[a.index()] {
+                    b = idom[b.index()].unwrap();
+                }
+            }
+            a
+        };
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &v in postorder.iter().rev().skip(1) {
+                let mut new_idom = None;
+                for &p in backward(v) {
+                    if idom[p.index()].is_none() {
+                        continue;
+                    }
+                    new_idom = Some(match new_idom {
+                        None => p,
+                        Some(current) => intersect(&idom, p, current),
+                    });
+                }
+                if new_idom != idom[v.index()] {
+                    idom[v.index()] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+        Self { root, idom }
+    }
+
+    pub fn root(&self) -> NodeId {
+        self.root
+    }
+
+    /// Whether `id` is reachable from the root (or, for post-dominators, reaches it).
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.idom[id.index()].is_some()
+    }
+
+    /// The closest strict dominator of `id`; `None` for the root and unreachable nodes.
+    pub fn immediate_dominator(&self, id: NodeId) -> Option<NodeId> {
+        self.idom[id.index()].filter(|_| id != self.root)
+    }
+
+    /// Every dominator of `id` from the root down to `id` itself, which is the
+    /// order every path meets them in. Empty if `id` is unreachable.
+    pub fn dominators(&self, id: NodeId) -> Vec<NodeId> {
+        if !self.contains(id) {
+            return Vec::new();
+        }
+        let mut chain = vec![id];
+        let mut current = id;
+        while let Some(parent) = self.immediate_dominator(current) {
+            chain.push(parent);
+            current = parent;
+        }
+        chain.reverse();
+        chain
+    }
+
+    pub fn dominates(&self, a: NodeId, b: NodeId) -> bool {
+        self.dominators(b).contains(&a)
+    }
+}
+
+/// The nodes every path from `from` to `to` goes through, in the order they are met.
+pub fn must_pass(graph: &Graph, from: NodeId, to: NodeId) -> Vec<NodeId> {
+    DominatorTree::new(graph, from).dominators(to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::test_support::{PART_2_EXAMPLE, names};
+
+    #[test]
+    fn day_11_example() {
+        let graph = Graph::parse(PART_2_EXAMPLE).unwrap();
+        let id = |name| graph.id(name).unwrap();
+        let must = must_pass(&graph, id("svr"), id("out"));
+        assert_eq!(names(&graph, &must), ["svr", "ccc", "fff", "out"]);
+
+        let tree = DominatorTree::new(&graph, id("svr"));
+        assert_eq!(tree.immediate_dominator(id("dac")), Some(id("eee")));
+        assert_eq!(tree.immediate_dominator(id("svr")), None);
+        assert!(tree.dominates(id("ccc"), id("hub")));
+        assert!(!tree.dominates(id("aaa"), id("ccc")));
+
+        let post = DominatorTree::post_dominators(&graph, id("out"));
+        assert_eq!(post.immediate_dominator(id("eee")), Some(id("dac")));
+        assert_eq!(post.immediate_dominator(id("svr")), Some(id("ccc")));
+        assert_eq!(
+            names(&graph, &post.dominators(id("aaa"))),
+            ["out", "fff", "ccc", "fft", "aaa"]
+        );
+    }
+
+    #[test]
+    fn loops_and_unreachable_nodes() {
+        // The loop b -> c -> b doesn't stop a from dominating everything after it.
+        let graph = Graph::parse("r: a x\na: b\nb: c d\nc: b\nx: d\nd: e\nz: e\n").unwrap();
+        let id = |name| graph.id(name).unwrap();
+        let tree = DominatorTree::new(&graph, id("r"));
+        assert_eq!(tree.immediate_dominator(id("c")), Some(id("b")));
+        assert_eq!(tree.immediate_dominator(id("d")), Some(id("r")));
+        assert_eq!(tree.immediate_dominator(id("e")), Some(id("d")));
+        assert!(!tree.contains(id("z")));
+        assert!(tree.dominators(id("z")).is_empty());
+    }
+}
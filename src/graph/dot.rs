@@ -0,0 +1,237 @@
+//! Graphviz DOT export, for looking at a graph instead of reading it.
+//!
+//! The output is plain text; render it with e.g. `dot -Tsvg graph.dot`.
+
+use super::paths::{paths_from, paths_to};
+use super::{Graph, NodeId};
+use crate::disjoint_set::DisjointSet;
+use crate::kdtree::KdTree;
+use crate::point::Point3;
+use anyhow::{Result, bail};
+use num_bigint::BigUint;
+use std::fmt::{self, Display, Write};
+
+/// Fill colors for components, reused in order when there are more components.
+const PALETTE: [&str; 8] = [
+    "lightblue",
+    "palegreen",
+    "gold",
+    "lightpink",
+    "plum",
+    "lightsalmon",
+    "aquamarine",
+    "khaki",
+];
+
+/// A [`Graph`] with drawing options, displayed as DOT.
+#[derive(Clone, Debug)]
+pub struct Dot<'a> {
+    graph: &'a Graph,
+    name: String,
+    directed: bool,
+    highlighted: Vec<bool>,
+    colors: Vec<Option<usize>>,
+    /// Paths from the source to each node and from each node to the target.
+    counts: Option<(Vec<BigUint>, Vec<BigUint>)>,
+}
+
+impl<'a> Dot<'a> {
+    pub fn new(graph: &'a Graph) -> Self {
+        Self {
+            graph,
+            name: "G".to_string(),
+            directed: true,
+            highlighted: vec![false; graph.len()],
+            colors: vec![None; graph.len()],
+            counts: None,
+        }
+    }
+
+    /// The graph's name in the output.
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Draws a `graph` with `--` edges instead of a `digraph`.
+    pub fn undirected(mut self) -> Self {
+        self.directed = false;
+        self
+    }
+
+    /// Draws these nodes with a bold red outline.
+    pub fn highlight(mut self, nodes: impl IntoIterator<Item = NodeId>) -> Self {
+        for id in nodes {
+            self.highlighted[id.index()] = true;
+        }
+        self
+    }
+
+    /// Fills the nodes of each set with more than one member in its own color.
+    /// Set elements are node indices.
+    pub fn components(mut self, sets: &mut DisjointSet) -> Self {
+        let groups = sets.components();
+        for (color, group) in groups.iter().filter(|g| g.len() > 1).enumerate() {
+            for &x in group {
+                self.colors[x] = Some(color % PALETTE.len());
+            }
+        }
+        self
+    }
+
+    /// Labels each edge with how many paths from `from` to `to` use it.
+    ///
+    /// Fails like [`paths_from`] and [`paths_to`] on cycles.
+    pub fn path_counts(mut self, from: NodeId, to: NodeId) -> Result<Self> {
+        self.counts = Some((paths_from(self.graph, from)?, paths_to(self.graph, to)?));
+        Ok(self)
+    }
+
+    fn node_attributes(&self, id: NodeId) -> Vec<String> {
+        let mut styles = Vec::new();
+        let mut attributes = Vec::new();
+        if let Some(color) = self.colors[id.index()] {
+            styles.push("filled");
+            attributes.push(format!("fillcolor={}", PALETTE[color]));
+        }
+        if self.highlighted[id.index()] {
+            styles.push("bold");
+            attributes.push("color=red".to_string());
+            attributes.push("penwidth=2".to_string());
+        }
+        if !styles.is_empty() {
+            attributes.insert(0, format!("style=\"{}\"", styles.join(",")));
+        }
+        attributes
+    }
+
+    fn edge_label(&self, a: NodeId, b: NodeId) -> Option<BigUint> {
+        let (before, after) = self.counts.as_ref()?;
+        let count = &before[a.index()] * &after[b.index()];
+        (count != BigUint::ZERO).then_some(count)
+    }
+}
+
+/// Day 08's junction boxes, named `x,y,z` and joined by the `pairs` closest
+/// connections, with the circuits those connections form. Draw it with
+/// [`Dot::undirected`] and [`Dot::components`].
+pub fn circuits(coords: &[Point3], pairs: usize) -> Result<(Graph, DisjointSet)> {
+    let mut graph = Graph::new();
+    let ids: Vec<NodeId> = coords
+        .iter()
+        .map(|p| graph.node(&format!("{},{},{}", p.x, p.y, p.z)))
+        .collect();
+    if graph.len() != coords.len() {
+        bail!("Junction boxes must have distinct coordinates");
+    }
+    let mut sets = DisjointSet::new(coords.len());
+    for (_, i, j) in KdTree::new(coords).pairs().take(pairs) {
+        graph.add_edge(ids[i], ids[j]);
+        sets.union(i, j);
+    }
+    Ok((graph, sets))
+}
+
+/// A quoted DOT identifier.
+fn quote(name: &str) -> String {
+    let mut quoted = String::with_capacity(name.len() + 2);
+    quoted.push('"');
+    for c in name.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl Display for Dot<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (keyword, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        writeln!(f, "{keyword} {} {{", quote(&self.name))?;
+        for id in self.graph.nodes() {
+            write!(f, "  {}", quote(self.graph.name(id)))?;
+            let attributes = self.node_attributes(id);
+            if !attributes.is_empty() {
+                write!(f, " [{}]", attributes.join(", "))?;
+            }
+            writeln!(f, ";")?;
+        }
+        for (a, b) in self.graph.edges() {
+            let mut line = format!(
+                "  {} {arrow} {}",
+                quote(self.graph.name(a)),
+                quote(self.graph.name(b))
+            );
+            if let Some(count) = self.edge_label(a, b) {
+                write!(line, " [label=\"{count}\"]")?;
+            }
+            writeln!(f, "{line};")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::test_support::PART_2_EXAMPLE;
+    use std::fs;
+
+    /// Compares against `tests/fixtures/<name>`, or rewrites it when
+    /// `UPDATE_GOLDEN` is set.
+    fn check_golden(name: &str, actual: &str) {
+        let path = format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            fs::write(&path, actual).unwrap();
+        }
+        let expected = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            actual, expected,
+            "{name} changed; rerun with UPDATE_GOLDEN=1 to accept"
+        );
+    }
+
+    #[test]
+    fn day_11_with_path_counts() {
+        let graph = Graph::parse(PART_2_EXAMPLE).unwrap();
+        let id = |name| graph.id(name).unwrap();
+        let dot = Dot::new(&graph)
+            .name("day 11")
+            .highlight(["svr", "dac", "fft", "out"].map(id))
+            .path_counts(id("svr"), id("out"))
+            .unwrap();
+        check_golden("day11.dot", &dot.to_string());
+    }
+
+    #[test]
+    fn day_08_circuits() {
+        let coords: Vec<Point3> = "\
+162,817,812 57,618,57 906,360,560 592,479,940 352,342,300 466,668,158 542,29,236
+431,825,988 739,650,466 52,470,668 216,146,977 819,987,18 117,168,530 805,96,715
+346,949,466 970,615,88 941,993,340 862,61,35 984,92,344 425,690,689"
+            .split_whitespace()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let (graph, mut sets) = circuits(&coords, 10).unwrap();
+        let dot = Dot::new(&graph)
+            .name("day 08")
+            .undirected()
+            .components(&mut sets);
+        check_golden("day08.dot", &dot.to_string());
+    }
+
+    #[test]
+    fn quoting() {
+        let graph = Graph::parse("a\"b: c\\d\n").unwrap();
+        assert_eq!(
+            Dot::new(&graph).to_string(),
+            "digraph \"G\" {\n  \"a\\\"b\";\n  \"c\\\\d\";\n  \"a\\\"b\" -> \"c\\\\d\";\n}\n"
+        );
+    }
+}
@@ -0,0 +1,349 @@
+//! Maximum flow with Dinic's algorithm, and what it says about disjoint paths
+//! and the cuts that separate two nodes.
+
+use super::{Graph, NodeId};
+use anyhow::{Result, bail};
+use std::collections::VecDeque;
+
+/// Capacity that never runs out.
+pub const UNLIMITED: u64 = u64::MAX;
+
+#[derive(Clone, Debug)]
+struct Edge {
+    to: usize,
+    /// Residual capacity.
+    capacity: u64,
+}
+
+/// A network of numbered nodes and capacitated edges.
+///
+/// Edge `k` is stored at `2k` with its residual twin at `2k + 1`.
+#[derive(Clone, Debug, Default)]
+pub struct FlowNetwork {
+    edges: Vec<Edge>,
+    capacities: Vec<u64>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+/// The outcome of [`FlowNetwork::max_flow`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Flow {
+    pub value: u64,
+    /// Nodes still reachable from the source in the residual network.
+    pub source_side: Vec<bool>,
+    /// Edges from the source side to the rest, in the order they were added.
+    /// Their capacities add up to `value`.
+    pub cut: Vec<usize>,
+}
+
+impl FlowNetwork {
+    pub fn new(nodes: usize) -> Self {
+        Self {
+            adjacency: vec![Vec::new(); nodes],
+            ..Self::default()
+        }
+    }
+
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.is_empty()
+    }
+
+    /// Adds an edge and returns its index.
+    pub fn add_edge(&mut self, from: usize, to: usize, capacity: u64) -> usize {
+        let k = self.capacities.len();
+        self.adjacency[from].push(2 * k);
+        self.adjacency[to].push(2 * k + 1);
+        self.edges.push(Edge { to, capacity });
+        self.edges.push(Edge {
+            to: from,
+            capacity: 0,
+        });
+        self.capacities.push(capacity);
+        k
+    }
+
+    /// The endpoints of edge `k`.
+    pub fn endpoints(&self, k: usize) -> (usize, usize) {
+        (self.edges[2 * k + 1].to, self.edges[2 * k].to)
+    }
+
+    /// Flow currently sent along edge `k`.
+    pub fn flow(&self, k: usize) -> u64 {
+        self.capacities[k] - self.edges[2 * k].capacity
+    }
+
+    /// Sends as much flow as possible from `source` to `sink`.
+    ///
+    /// Meant to be called once; a second call only finds flow the first one left.
+    pub fn max_flow(&mut self, source: usize, sink: usize) -> Flow {
+        assert_ne!(source, sink, "source and sink must differ");
+        let mut value = 0u64;
+        loop {
+            let level = self.levels(source);
+            if level[sink] == usize::MAX {
+                let source_side: Vec<bool> = level.iter().map(|&l| l != usize::MAX).collect();
+                let cut = (0..self.capacities.len())
+                    .filter(|&k| {
+                        let (from, to) = self.endpoints(k);
+                        source_side[from] && !source_side[to]
+                    })
+                    .collect();
+                return Flow {
+                    value,
+                    source_side,
+                    cut,
+                };
+            }
+            let mut next = vec![0; self.len()];
+            loop {
+                let pushed = self.augment(source, sink, &level, &mut next);
+                if pushed == 0 {
+                    break;
+                }
+                value = value.saturating_add(pushed);
+            }
+        }
+    }
+
+    /// Breadth-first distances from `source` over edges with capacity left.
+    fn levels(&self, source: usize) -> Vec<usize> {
+        let mut level = vec![usize::MAX; self.len()];
+        level[source] = 0;
+        let mut queue = VecDeque::from([source]);
+        while let Some(v) = queue.pop_front() {
+            for &e in &self.adjacency[v] {
+                let Edge { to, capacity } = self.edges[e];
+                if capacity > 0 && level[to] == usize::MAX {
+                    level[to] = level[v] + 1;
+                    queue.push_back(to);
+                }
+            }
+        }
+        level
+    }
+
+    /// Pushes flow along one shortest augmenting path, returning how much.
+    /// `next` remembers, per node, the first edge that may still lead to the sink.
+    fn augment(&mut self, source: usize, sink: usize, level: &[usize], next: &mut [usize]) -> u64 {
+        let mut path: Vec<usize> = Vec::new();
+        let mut v = source;
+        loop {
+            if v == sink {
+                let bottleneck = path.iter().map(|&e| self.edges[e].capacity).min().unwrap();
+                for &e in &path {
+                    self.edges[e].capacity -= bottleneck;
+                    self.edges[e ^ 1].capacity += bottleneck;
+                }
+                return bottleneck;
+            }
+            let adjacency = &self.adjacency[v];
+            while let Some(&e) = adjacency.get(next[v]) {
+                let Edge { to, capacity } = self.edges[e];
+                if capacity > 0 && level[to] == level[v] + 1 {
+                    break;
+                }
+                next[v] += 1;
+            }
+            if let Some(&e) = adjacency.get(next[v]) {
+                path.push(e);
+                v = self.edges[e].to;
+            } else {
+                // Dead end: back up and skip the edge that led here.
+                let Some(e) = path.pop() else {
+                    return 0;
+                };
+                v = self.edges[e ^ 1].to;
+                next[v] += 1;
+            }
+        }
+    }
+}
+
+/// How many disjoint paths connect two nodes, and a smallest set of
+/// nodes or edges whose removal leaves none.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Separation<T> {
+    pub paths: u64,
+    pub cut: Vec<T>,
+    /// Paths that removing `cut` leaves connected because nothing of that kind
+    /// can block them: edges straight from one node to the other, when cutting nodes.
+    pub unblockable: u64,
+}
+
+impl<T> Separation<T> {
+    /// Whether removing `cut` really disconnects the two nodes.
+    pub fn separates(&self) -> bool {
+        self.unblockable == 0
+    }
+}
+
+/// Paths from `from` to `to` that share no edge, and a smallest set of edges
+/// that disconnects them.
+pub fn edge_disjoint_paths(
+    graph: &Graph,
+    from: NodeId,
+    to: NodeId,
+) -> Result<Separation<(NodeId, NodeId)>> {
+    if from == to {
+        bail!("Disjoint paths need two different nodes");
+    }
+    let edges: Vec<(NodeId, NodeId)> = graph.edges().collect();
+    let mut network = FlowNetwork::new(graph.len());
+    for &(a, b) in &edges {
+        network.add_edge(a.index(), b.index(), 1);
+    }
+    let flow = network.max_flow(from.index(), to.index());
+    Ok(Separation {
+        paths: flow.value,
+        cut: flow.cut.into_iter().map(|k| edges[k]).collect(),
+        unblockable: 0,
+    })
+}
+
+/// Paths from `from` to `to` that share no node besides the two ends, and a
+/// smallest set of other nodes that disconnects them.
+///
+/// Each node is split into an entry and an exit joined by an edge of capacity
+/// one. An edge straight from `from` to `to` counts as a path that no node can
+/// block; such edges are counted in [`Separation::unblockable`] and the cut
+/// leaves them connected.
+pub fn node_disjoint_paths(graph: &Graph, from: NodeId, to: NodeId) -> Result<Separation<NodeId>> {
+    if from == to {
+        bail!("Disjoint paths need two different nodes");
+    }
+    let entry = |id: NodeId| 2 * id.index();
+    let exit = |id: NodeId| 2 * id.index() + 1;
+    let mut network = FlowNetwork::new(2 * graph.len());
+    for id in graph.nodes() {
+        let capacity = if id == from || id == to { UNLIMITED } else { 1 };
+        network.add_edge(entry(id), exit(id), capacity);
+    }
+    let mut unblockable = 0;
+    for (a, b) in graph.edges() {
+        let direct = a == from && b == to;
+        unblockable += u64::from(direct);
+        let capacity = if direct { 1 } else { UNLIMITED };
+        network.add_edge(exit(a), entry(b), capacity);
+    }
+    let flow = network.max_flow(exit(from), entry(to));
+    // The first `graph.len()` edges are the splits, in node order.
+    let cut = flow
+        .cut
+        .into_iter()
+        .filter(|&k| k < graph.len())
+        .map(|k| NodeId(k as u32))
+        .collect();
+    Ok(Separation {
+        paths: flow.value,
+        cut,
+        unblockable,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::test_support::{PART_1_EXAMPLE, names};
+
+    #[test]
+    fn textbook_network() {
+        let mut network = FlowNetwork::new(6);
+        let (s, v1, v2, v3, v4, t) = (0, 1, 2, 3, 4, 5);
+        for (from, to, capacity) in [
+            (s, v1, 16),
+            (s, v2, 13),
+            (v1, v3, 12),
+            (v2, v1, 4),
+            (v2, v4, 14),
+            (v3, v2, 9),
+            (v3, t, 20),
+            (v4, v3, 7),
+            (v4, t, 4),
+        ] {
+            network.add_edge(from, to, capacity);
+        }
+        let flow = network.max_flow(s, t);
+        assert_eq!(flow.value, 23);
+        assert_eq!(flow.source_side, [true, true, true, false, true, false]);
+        assert_eq!(flow.cut, [2, 7, 8]);
+        assert_eq!(network.flow(2) + network.flow(7) + network.flow(8), 23);
+        assert_eq!(network.endpoints(7), (v4, v3));
+    }
+
+    #[test]
+    fn day_11_example() {
+        let graph = Graph::parse(PART_1_EXAMPLE).unwrap();
+        let id = |name| graph.id(name).unwrap();
+
+        let edges = edge_disjoint_paths(&graph, id("you"), id("out")).unwrap();
+        assert_eq!(edges.paths, 2);
+        assert_eq!(edges.cut, [(id("you"), id("bbb")), (id("you"), id("ccc"))]);
+
+        let nodes = node_disjoint_paths(&graph, id("you"), id("out")).unwrap();
+        assert_eq!(
+            nodes,
+            Separation {
+                paths: 2,
+                cut: vec![id("bbb"), id("ccc")],
+                unblockable: 0,
+            }
+        );
+
+        // From aaa the routes through you and hhh can't share anything.
+        let nodes = node_disjoint_paths(&graph, id("aaa"), id("out")).unwrap();
+        assert_eq!(nodes.paths, 2);
+        assert_eq!(names(&graph, &nodes.cut), ["you", "hhh"]);
+        let nodes = node_disjoint_paths(&graph, id("hhh"), id("out")).unwrap();
+        assert_eq!(nodes.paths, 3);
+        assert_eq!(names(&graph, &nodes.cut), ["ccc", "fff", "iii"]);
+
+        assert!(edge_disjoint_paths(&graph, id("out"), id("out")).is_err());
+    }
+
+    #[test]
+    fn bottlenecks_and_direct_edges() {
+        // Everything funnels through m, plus one edge straight to t.
+        let graph = Graph::parse("s: a b t\na: m\nb: m\nm: c d\nc: t\nd: t\n").unwrap();
+        let id = |name| graph.id(name).unwrap();
+        let nodes = node_disjoint_paths(&graph, id("s"), id("t")).unwrap();
+        assert_eq!(
+            nodes,
+            Separation {
+                paths: 2,
+                cut: vec![id("m")],
+                unblockable: 1,
+            }
+        );
+        let edges = edge_disjoint_paths(&graph, id("s"), id("t")).unwrap();
+        assert_eq!(edges.paths, 3);
+        assert_eq!(edges.cut.len(), 3);
+
+        let unreachable = node_disjoint_paths(&graph, id("t"), id("s")).unwrap();
+        assert_eq!(
+            unreachable,
+            Separation {
+                paths: 0,
+                cut: vec![],
+                unblockable: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn direct_edges_cannot_be_cut_by_nodes() {
+        let graph = Graph::parse("you: out a\na: out\n").unwrap();
+        let id = |name| graph.id(name).unwrap();
+        let nodes = node_disjoint_paths(&graph, id("you"), id("out")).unwrap();
+        assert_eq!(nodes.paths, 2);
+        assert_eq!(nodes.cut, [id("a")]);
+        assert!(!nodes.separates());
+        let edges = edge_disjoint_paths(&graph, id("you"), id("out")).unwrap();
+        assert_eq!(edges.cut, [(id("you"), id("out")), (id("you"), id("a"))]);
+        assert!(edges.separates());
+    }
+}
@@ -0,0 +1,372 @@
+//! Counting paths in a DAG in one pass over a topological order.
+//!
+//! The number type decides what happens when counts get big: plain integers
+//! fail on overflow, [`Modular`] wraps, and [`BigUint`] never runs out.
+
+use super::{Graph, NodeId, scc};
+use anyhow::{Result, bail};
+use num_bigint::BigUint;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// A number paths can be counted in.
+pub trait PathCount: Clone + PartialEq {
+    fn zero() -> Self;
+    fn one() -> Self;
+    /// `self + other`, or an error if it can't be represented.
+    fn checked_sum(&self, other: &Self) -> Result<Self>;
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+}
+
+macro_rules! checked_count {
+    ($($t:ty),*) => {
+        $(
+            impl PathCount for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn checked_sum(&self, other: &Self) -> Result<Self> {
+                    match self.checked_add(*other) {
+                        Some(sum) => Ok(sum),
+                        None => bail!("Path count overflows {}", stringify!($t)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+checked_count!(u32, u64, u128, usize);
+
+impl PathCount for BigUint {
+    fn zero() -> Self {
+        BigUint::ZERO
+    }
+
+    fn one() -> Self {
+        BigUint::from(1u8)
+    }
+
+    fn checked_sum(&self, other: &Self) -> Result<Self> {
+        Ok(self + other)
+    }
+}
+
+/// A count modulo `M`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modular<const M: u64>(pub u64);
+
+impl<const M: u64> PathCount for Modular<M> {
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    fn one() -> Self {
+        Self(1 % M)
+    }
+
+    fn checked_sum(&self, other: &Self) -> Result<Self> {
+        let sum = (u128::from(self.0) + u128::from(other.0)) % u128::from(M);
+        Ok(Self(sum as u64))
+    }
+}
+
+impl<const M: u64> Display for Modular<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A cycle lies on some path being counted, so there are infinitely many.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InfinitePaths {
+    /// Names of the nodes around the cycle, first one not repeated.
+    pub cycle: Vec<String>,
+}
+
+impl Display for InfinitePaths {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let around: Vec<&str> = self
+            .cycle
+            .iter()
+            .chain(self.cycle.first())
+            .map(String::as_str)
+            .collect();
+        write!(
+            f,
+            "Infinitely many paths through the cycle {}",
+            around.join(" -> ")
+        )
+    }
+}
+
+impl Error for InfinitePaths {}
+
+/// Topological order of `include`, or [`InfinitePaths`] naming a cycle among them.
+fn acyclic_order(graph: &Graph, include: &[bool]) -> Result<Vec<NodeId>> {
+    graph.topological_order_of(include).map_err(|_| {
+        // Only a failed sort pays for looking up the cycle again.
+        let cycle =
+            scc::find_cycle(graph, include).expect("a topological sort fails only on a cycle");
+        let cycle = cycle.iter().map(|&id| graph.name(id).to_string()).collect();
+        InfinitePaths { cycle }.into()
+    })
+}
+
+/// Nodes on some path from `from` to `to` that stays off the nodes marked in `avoid`.
+fn between(graph: &Graph, from: NodeId, to: NodeId, avoid: &[bool]) -> Vec<bool> {
+    graph
+        .reachable_avoiding(from, avoid)
+        .into_iter()
+        .zip(graph.reaching_avoiding(to, avoid))
+        .map(|(a, b)| a && b)
+        .collect()
+}
+
+/// Number of paths from `from` to every node, indexed by [`NodeId::index`].
+///
+/// Only the part of the graph reachable from `from` has to be acyclic.
+pub fn paths_from<C: PathCount>(graph: &Graph, from: NodeId) -> Result<Vec<C>> {
+    count(graph, from, false)
+}
+
+/// Number of paths from every node to `to`.
+///
+/// Only the part of the graph that can reach `to` has to be acyclic.
+pub fn paths_to<C: PathCount>(graph: &Graph, to: NodeId) -> Result<Vec<C>> {
+    count(graph, to, true)
+}
+
+/// Number of paths from `from` to `to`.
+///
+/// Only the nodes on such paths have to be acyclic.
+pub fn count_paths<C: PathCount>(graph: &Graph, from: NodeId, to: NodeId) -> Result<C> {
+    let avoid = vec![false; graph.len()];
+    let mut counts = propagate::<C>(graph, from, &between(graph, from, to, &avoid))?;
+    Ok(counts.swap_remove(to.index()))
+}
+
+/// Number of paths from `from` to `to` that visit every node in `required`, in any order.
+pub fn count_paths_via<C: PathCount>(
+    graph: &Graph,
+    from: NodeId,
+    to: NodeId,
+    required: &[NodeId],
+) -> Result<C> {
+    count_paths_via_avoiding(graph, from, to, required, &[])
+}
+
+/// Like [`count_paths_via`], but only counting paths that stay off every node in `avoid`.
+///
+/// Runs a single pass over (node, subset of `required` seen so far), so the cost
+/// grows with 2^`required.len()` rather than with the number of orderings.
+pub fn count_paths_via_avoiding<C: PathCount>(
+    graph: &Graph,
+    from: NodeId,
+    to: NodeId,
+    required: &[NodeId],
+    avoid: &[NodeId],
+) -> Result<C> {
+    let mut bit = vec![0usize; graph.len()];
+    for &id in required {
+        let next = 1 << bit.iter().filter(|&&b| b != 0).count();
+        if bit[id.index()] == 0 {
+            bit[id.index()] = next;
+        }
+    }
+    let subsets = 1 << bit.iter().filter(|&&b| b != 0).count();
+    if subsets > 1 << 20 {
+        bail!("Too many required nodes: {}", required.len());
+    }
+
+    let mut avoided = vec![false; graph.len()];
+    for &id in avoid {
+        avoided[id.index()] = true;
+    }
+    // Cycles only reachable through an avoided node must not count.
+    let include = between(graph, from, to, &avoided);
+    if !include[from.index()] || !include[to.index()] {
+        return Ok(C::zero());
+    }
+
+    let order = acyclic_order(graph, &include)?;
+    let mut counts = vec![Vec::new(); graph.len()];
+    for &id in &order {
+        counts[id.index()] = vec![C::zero(); subsets];
+    }
+    counts[from.index()][bit[from.index()]] = C::one();
+    for id in order {
+        for &next in graph.successors(id) {
+            if !include[next.index()] {
+                continue;
+            }
+            for seen in 0..subsets {
+                let count = &counts[id.index()][seen];
+                if count.is_zero() {
+                    continue;
+                }
+                let sum = counts[next.index()][seen | bit[next.index()]].checked_sum(count)?;
+                counts[next.index()][seen | bit[next.index()]] = sum;
+            }
+        }
+    }
+    Ok(counts[to.index()][subsets - 1].clone())
+}
+
+fn count<C: PathCount>(graph: &Graph, start: NodeId, backwards: bool) -> Result<Vec<C>> {
+    let graph = if backwards { &graph.reversed() } else { graph };
+    propagate(graph, start, &graph.reachable_from(start))
+}
+
+/// Counts paths from `start` along edges between nodes in `include`.
+fn propagate<C: PathCount>(graph: &Graph, start: NodeId, include: &[bool]) -> Result<Vec<C>> {
+    let mut counts = vec![C::zero(); graph.len()];
+    if !include[start.index()] {
+        return Ok(counts);
+    }
+    counts[start.index()] = C::one();
+    for id in acyclic_order(graph, include)? {
+        if counts[id.index()].is_zero() {
+            continue;
+        }
+        for &next in graph.successors(id) {
+            if include[next.index()] {
+                counts[next.index()] = counts[next.index()].checked_sum(&counts[id.index()])?;
+            }
+        }
+    }
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::test_support::PART_2_EXAMPLE;
+
+    /// A chain of diamonds: 2^n paths from `n0` to the last node.
+    fn diamonds(n: usize) -> Graph {
+        let mut text = String::new();
+        for i in 0..n {
+            text += &format!("n{i}: a{i} b{i}\na{i}: n{}\nb{i}: n{}\n", i + 1, i + 1);
+        }
+        Graph::parse(&text).unwrap()
+    }
+
+    #[test]
+    fn counts_from_and_to() {
+        let graph = Graph::parse("you: bbb ccc\nbbb: out\nccc: bbb out\n").unwrap();
+        let you = graph.id("you").unwrap();
+        let bbb = graph.id("bbb").unwrap();
+        let out = graph.id("out").unwrap();
+        assert_eq!(paths_from::<u64>(&graph, you).unwrap(), [1, 2, 1, 3]);
+        assert_eq!(paths_to::<u64>(&graph, out).unwrap(), [3, 1, 2, 1]);
+        assert_eq!(count_paths::<u64>(&graph, bbb, you).unwrap(), 0);
+    }
+
+    #[test]
+    fn overflow_handling() {
+        let graph = diamonds(70);
+        let start = graph.id("n0").unwrap();
+        let end = graph.id("n70").unwrap();
+        let err = count_paths::<u64>(&graph, start, end).unwrap_err();
+        assert_eq!(err.to_string(), "Path count overflows u64");
+        assert_eq!(count_paths::<u128>(&graph, start, end).unwrap(), 1 << 70);
+        let big: BigUint = count_paths(&graph, start, end).unwrap();
+        assert_eq!(big, BigUint::from(1u8) << 70);
+        let modular: Modular<1_000_000_007> = count_paths(&graph, start, end).unwrap();
+        assert_eq!(u128::from(modular.0), (1u128 << 70) % 1_000_000_007);
+    }
+
+    #[test]
+    fn cycles_elsewhere_are_ignored() {
+        let graph = Graph::parse("a: b\nb: out\nx: y\ny: x out\n").unwrap();
+        let a = graph.id("a").unwrap();
+        let out = graph.id("out").unwrap();
+        assert_eq!(count_paths::<u64>(&graph, a, out).unwrap(), 1);
+        assert!(paths_to::<u64>(&graph, out).is_err());
+    }
+
+    #[test]
+    fn waypoints() {
+        // Day 11's second example: two paths from svr to out pass both dac and fft.
+        let graph = Graph::parse(PART_2_EXAMPLE).unwrap();
+        let id = |name| graph.id(name).unwrap();
+        let (svr, out) = (id("svr"), id("out"));
+        let via = |required: &[NodeId], avoid: &[NodeId]| {
+            count_paths_via_avoiding::<u64>(&graph, svr, out, required, avoid).unwrap()
+        };
+        assert_eq!(via(&[], &[]), 8);
+        assert_eq!(via(&[id("dac"), id("fft")], &[]), 2);
+        assert_eq!(via(&[id("fft"), id("dac"), id("fft")], &[]), 2);
+        assert_eq!(via(&[id("dac")], &[id("fft")]), 2);
+        assert_eq!(via(&[id("ccc"), id("fff")], &[id("aaa"), id("hhh")]), 2);
+        assert_eq!(via(&[svr, out], &[]), 8);
+        assert_eq!(via(&[], &[out]), 0);
+        assert_eq!(
+            count_paths_via::<u64>(&graph, out, svr, &[id("dac")]).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn reports_cycles_on_the_way() {
+        // The b <-> c cycle lies between a and out; x <-> y only leads away from out.
+        let graph = Graph::parse("a: b x\nb: c out\nc: b\nx: y\ny: x\n").unwrap();
+        let a = graph.id("a").unwrap();
+        let out = graph.id("out").unwrap();
+        let err = count_paths::<u64>(&graph, a, out).unwrap_err();
+        let cycle = err.downcast_ref::<InfinitePaths>().unwrap();
+        assert_eq!(cycle.cycle, ["b", "c"]);
+        assert_eq!(
+            err.to_string(),
+            "Infinitely many paths through the cycle b -> c -> b"
+        );
+        let c = graph.id("c").unwrap();
+        assert_eq!(
+            count_paths_via_avoiding::<u64>(&graph, a, out, &[], &[c]).unwrap(),
+            1
+        );
+        let x = graph.id("x").unwrap();
+        assert!(paths_from::<u64>(&graph, x).is_err());
+    }
+
+    #[test]
+    fn cycles_behind_avoided_nodes_are_ignored() {
+        let graph = Graph::parse(
+            "a: x out
+x: y
+y: z
+z: y out
+",
+        )
+        .unwrap();
+        let id = |name| graph.id(name).unwrap();
+        let (a, out) = (id("a"), id("out"));
+        assert!(count_paths::<u64>(&graph, a, out).is_err());
+        assert_eq!(
+            count_paths_via_avoiding::<u64>(&graph, a, out, &[], &[id("x")]).unwrap(),
+            1
+        );
+        assert_eq!(
+            count_paths_via_avoiding::<u64>(&graph, a, out, &[], &[a]).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn deep_chain() {
+        let n = 200_000;
+        let text: String = (0..n).map(|i| format!("n{i}: n{}\n", i + 1)).collect();
+        let graph = Graph::parse(&text).unwrap();
+        let last = graph.id(&format!("n{n}")).unwrap();
+        assert_eq!(count_paths::<u64>(&graph, NodeId(0), last).unwrap(), 1);
+    }
+}
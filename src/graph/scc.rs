@@ -0,0 +1,237 @@
+//! Strongly connected components (Tarjan's algorithm, without recursion) and
+//! the DAG of components they form.
+
+use super::{Graph, NodeId};
+use std::collections::VecDeque;
+
+/// The strongly connected components of `graph`, in topological order: every
+/// edge between two components goes from an earlier one to a later one.
+pub fn strongly_connected_components(graph: &Graph) -> Vec<Vec<NodeId>> {
+    components_of(graph, &vec![true; graph.len()])
+}
+
+/// Like [`strongly_connected_components`] for the nodes marked in `include` only.
+pub fn components_of(graph: &Graph, include: &[bool]) -> Vec<Vec<NodeId>> {
+    const UNVISITED: usize = usize::MAX;
+    let n = graph.len();
+    let mut index = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+    let mut next_index = 0;
+
+    for root in graph.nodes() {
+        if !include[root.index()] || index[root.index()] != UNVISITED {
+            continue;
+        }
+        // Each frame is a node and how many of its successors have been tried.
+        let mut calls = vec![(root, 0)];
+        index[root.index()] = next_index;
+        low[root.index()] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root.index()] = true;
+
+        while let Some(&mut (v, ref mut tried)) = calls.last_mut() {
+            let successors = graph.successors(v);
+            if let Some(&w) = successors.get(*tried) {
+                *tried += 1;
+                if !include[w.index()] {
+                    continue;
+                }
+                if index[w.index()] == UNVISITED {
+                    index[w.index()] = next_index;
+                    low[w.index()] = next_index;
+                    next_index += 1;
+                    stack.push(w);
+                    on_stack[w.index()] = true;
+                    calls.push((w, 0));
+                } else if on_stack[w.index()] {
+                    low[v.index()] = low[v.index()].min(index[w.index()]);
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                low[parent.index()] = low[parent.index()].min(low[v.index()]);
+            }
+            if low[v.index()] == index[v.index()] {
+                let mut component = Vec::new();
+                while let Some(w) = stack.pop() {
+                    on_stack[w.index()] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                components.push(component);
+            }
+        }
+    }
+    // Tarjan finishes sink components first.
+    components.reverse();
+    components
+}
+
+/// Some cycle among the nodes marked in `include`, as the nodes along it
+/// without repeating the first, or `None` if they form a DAG.
+pub fn find_cycle(graph: &Graph, include: &[bool]) -> Option<Vec<NodeId>> {
+    components_of(graph, include)
+        .iter()
+        .find_map(|component| cycle_within(graph, component))
+}
+
+/// A cycle through the first node of a strongly connected component, if it has one.
+fn cycle_within(graph: &Graph, component: &[NodeId]) -> Option<Vec<NodeId>> {
+    let start = component[0];
+    if graph.successors(start).contains(&start) {
+        return Some(vec![start]);
+    }
+    if component.len() == 1 {
+        return None;
+    }
+    let mut inside = vec![false; graph.len()];
+    for &id in component {
+        inside[id.index()] = true;
+    }
+    // Breadth-first from `start` until an edge leads back to it.
+    let mut parent = vec![None; graph.len()];
+    let mut queue = VecDeque::from([start]);
+    while let Some(v) = queue.pop_front() {
+        for &w in graph.successors(v) {
+            if w == start {
+                let mut cycle = vec![v];
+                while let Some(p) = parent[cycle.last().unwrap().index()] {
+                    cycle.push(p);
+                }
+                cycle.reverse();
+                return Some(cycle);
+            }
+            if inside[w.index()] && parent[w.index()].is_none() {
+                parent[w.index()] = Some(v);
+                queue.push_back(w);
+            }
+        }
+    }
+    unreachable!("a strongly connected component with several nodes has a cycle")
+}
+
+/// The graph with each strongly connected component collapsed to one node.
+#[derive(Clone, Debug)]
+pub struct Condensation {
+    component: Vec<usize>,
+    members: Vec<Vec<NodeId>>,
+    successors: Vec<Vec<usize>>,
+    cyclic: Vec<bool>,
+}
+
+impl Condensation {
+    /// Components are numbered in topological order, so edges always go to a higher number.
+    pub fn new(graph: &Graph) -> Self {
+        let members = strongly_connected_components(graph);
+        let mut component = vec![0; graph.len()];
+        for (c, ids) in members.iter().enumerate() {
+            for &id in ids {
+                component[id.index()] = c;
+            }
+        }
+        let mut successors = vec![Vec::new(); members.len()];
+        let mut cyclic: Vec<bool> = members.iter().map(|ids| ids.len() > 1).collect();
+        for (from, to) in graph.edges() {
+            let (a, b) = (component[from.index()], component[to.index()]);
+            if a == b {
+                cyclic[a] = true;
+            } else {
+                successors[a].push(b);
+            }
+        }
+        for list in &mut successors {
+            list.sort_unstable();
+            list.dedup();
+        }
+        Self {
+            component,
+            members,
+            successors,
+            cyclic,
+        }
+    }
+
+    /// Number of components.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn component_of(&self, id: NodeId) -> usize {
+        self.component[id.index()]
+    }
+
+    /// The nodes in component `c`, in id order.
+    pub fn members(&self, c: usize) -> &[NodeId] {
+        &self.members[c]
+    }
+
+    /// Components that component `c` has edges into.
+    pub fn successors(&self, c: usize) -> &[usize] {
+        &self.successors[c]
+    }
+
+    /// Whether component `c` contains a cycle: several nodes, or one with an edge to itself.
+    pub fn is_cyclic(&self, c: usize) -> bool {
+        self.cyclic[c]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::test_support::names;
+
+    #[test]
+    fn components_in_topological_order() {
+        let graph = Graph::parse("a: b\nb: c\nc: a d\nd: e\ne: d f\nf: f\n").unwrap();
+        let components: Vec<Vec<String>> = strongly_connected_components(&graph)
+            .iter()
+            .map(|c| names(&graph, c))
+            .collect();
+        assert_eq!(components, [vec!["a", "b", "c"], vec!["d", "e"], vec!["f"]]);
+
+        let condensed = Condensation::new(&graph);
+        assert_eq!(condensed.len(), 3);
+        assert_eq!(condensed.component_of(graph.id("e").unwrap()), 1);
+        assert_eq!(condensed.successors(0), [1]);
+        assert!(condensed.is_cyclic(2));
+        assert!((0..condensed.len()).all(|c| condensed.successors(c).iter().all(|&s| s > c)));
+    }
+
+    #[test]
+    fn cycles() {
+        let graph = Graph::parse("a: b\nb: c\nc: d\nd: b\nx: x\n").unwrap();
+        let mut include = vec![true; graph.len()];
+        include[graph.id("x").unwrap().index()] = false;
+        let cycle = find_cycle(&graph, &include).unwrap();
+        assert_eq!(names(&graph, &cycle), ["b", "c", "d"]);
+        include[graph.id("c").unwrap().index()] = false;
+        assert_eq!(find_cycle(&graph, &include), None);
+        include[graph.id("x").unwrap().index()] = true;
+        let cycle = find_cycle(&graph, &include).unwrap();
+        assert_eq!(names(&graph, &cycle), ["x"]);
+    }
+
+    #[test]
+    fn long_chain_does_not_recurse() {
+        let n = 200_000;
+        let text: String = (0..n)
+            .map(|i| format!("n{i}: n{}\n", (i + 1) % n))
+            .collect();
+        let graph = Graph::parse(&text).unwrap();
+        assert_eq!(strongly_connected_components(&graph).len(), 1);
+    }
+}
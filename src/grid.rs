@@ -0,0 +1,388 @@
+//! Dense rectangular grid with flat row-major storage.
+//!
+//! Cells are addressed by `(x, y)` with `x` the column and `y` the row, both from the top left.
+
+use crate::direction::Direction;
+use crate::point::Point2;
+use anyhow::{Result, bail};
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+const OFFSETS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Wraps row-major `cells`. Panics if there are not `width * height` of them.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "grid is {width}x{height}");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// One cell per character, converted by `f`. Lines must all be the same length.
+    pub fn parse_with(text: &str, mut f: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in text.lines() {
+            let before = cells.len();
+            for c in line.chars() {
+                cells.push(f(c)?);
+            }
+            let len = cells.len() - before;
+            match width {
+                None => width = Some(len),
+                Some(w) if w != len => {
+                    bail!("Line {} has {len} cells, expected {w}", height + 1)
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.in_bounds(x, y)
+            .then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.in_bounds(x, y) {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// The cell at a point with signed coordinates, if that is inside the grid.
+    pub fn get_point<C: TryInto<usize> + Copy>(&self, p: Point2<C>) -> Option<&T> {
+        let (x, y) = p.to_index()?;
+        self.get(x, y)
+    }
+
+    /// The cell at `(x + dx, y + dy)`, if that is inside the grid.
+    pub fn offset(&self, x: usize, y: usize, dx: isize, dy: isize) -> Option<(usize, usize)> {
+        let nx = x.checked_add_signed(dx)?;
+        let ny = y.checked_add_signed(dy)?;
+        self.in_bounds(nx, ny).then_some((nx, ny))
+    }
+
+    /// The point one step from `p` in direction `dir`, if that is inside the grid.
+    pub fn step(&self, p: Point2<usize>, dir: Direction) -> Option<Point2<usize>> {
+        let (dx, dy) = dir.offset();
+        self.offset(p.x, p.y, dx, dy).map(Point2::from)
+    }
+
+    /// Orthogonal neighbours inside the grid: up, right, down, left.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Direction::ALL.into_iter().filter_map(move |d| {
+            let (dx, dy) = d.offset();
+            self.offset(x, y, dx, dy)
+        })
+    }
+
+    /// Orthogonal and diagonal neighbours inside the grid, row by row.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS8
+            .iter()
+            .filter_map(move |&(dx, dy)| self.offset(x, y, dx, dy))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All coordinates in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell with its coordinates, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|(_, v)| *v == value).map(|(p, _)| p)
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, v)| *v == value)
+            .map(|(p, _)| p)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// A new grid of the given size whose cell `(x, y)` is `self[from(x, y)]`.
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        from: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self[from(x, y)].clone())
+            .collect();
+        Self::from_vec(width, height, cells)
+    }
+
+    /// Rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotated a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let h = self.height;
+        self.remap(self.height, self.width, |x, y| (y, h - 1 - x))
+    }
+
+    /// Rotated a quarter turn counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let w = self.width;
+        self.remap(self.height, self.width, |x, y| (w - 1 - y, x))
+    }
+
+    /// Mirrored left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        let w = self.width;
+        self.remap(self.width, self.height, |x, y| (w - 1 - x, y))
+    }
+
+    /// Mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        let h = self.height;
+        self.remap(self.width, self.height, |x, y| (x, h - 1 - y))
+    }
+}
+
+impl Grid<char> {
+    /// One cell per character. Lines must all be the same length.
+    pub fn parse(text: &str) -> Result<Self> {
+        Self::parse_with(text, Ok)
+    }
+
+    /// One cell per character, padding short lines on the right with `fill`.
+    pub fn parse_padded(text: &str, fill: char) -> Self {
+        let width = text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in text.lines() {
+            let before = cells.len();
+            cells.extend(line.chars());
+            cells.resize(before + width, fill);
+            height += 1;
+        }
+        Self::from_vec(width, height, cells)
+    }
+}
+
+impl Grid<u8> {
+    /// One cell per byte. Lines must all be the same length.
+    pub fn parse_bytes(text: &str) -> Result<Self> {
+        if !text.is_ascii() {
+            bail!("Grid text is not ASCII");
+        }
+        Self::parse_with(text, |c| Ok(c as u8))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(self.in_bounds(x, y), "({x}, {y}) out of bounds");
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(self.in_bounds(x, y), "({x}, {y}) out of bounds");
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T> Index<Point2<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2<usize>) -> &T {
+        &self[(p.x, p.y)]
+    }
+}
+
+impl<T> IndexMut<Point2<usize>> for Grid<T> {
+    fn index_mut(&mut self, p: Point2<usize>) -> &mut T {
+        &mut self[(p.x, p.y)]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "abc\ndef\n";
+
+    #[test]
+    fn parse_and_display() {
+        let grid = Grid::parse(TEXT).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid[Point2::new(1, 0)], 'b');
+        assert_eq!(grid.get_point(Point2::new(2i64, 1)), Some(&'f'));
+        assert_eq!(grid.get_point(Point2::new(-1i64, 1)), None);
+        assert_eq!(grid.to_string(), TEXT);
+        assert!(Grid::parse("ab\nc\n").is_err());
+        let padded = Grid::parse_padded("ab\nc\n", '.');
+        assert_eq!(padded.to_string(), "ab\nc.\n");
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        let corner: Vec<_> = grid.neighbors4(0, 0).collect();
+        assert_eq!(corner, [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors8(0, 0).count(), 3);
+        assert_eq!(grid.neighbors8(1, 1).count(), 8);
+        assert_eq!(grid.neighbors4(2, 1).count(), 3);
+        assert_eq!(
+            grid.step(Point2::new(2, 1), Direction::Left),
+            Some(Point2::new(1, 1))
+        );
+        assert_eq!(grid.step(Point2::new(2, 1), Direction::Right), None);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::parse(TEXT).unwrap();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        let columns: Vec<String> = grid.columns().map(|c| c.collect()).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        assert_eq!(grid.find(&'e'), Some((1, 1)));
+        assert_eq!(grid.find(&'z'), None);
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = Grid::parse(TEXT).unwrap();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(
+            grid.rotate_cw().rotate_cw(),
+            grid.flip_horizontal().flip_vertical()
+        );
+    }
+}
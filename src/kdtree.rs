@@ -0,0 +1,273 @@
+//! Static k-d tree over [`Point3`]s for nearest-neighbour queries.
+//!
+//! Distances are squared Euclidean, and results are ordered by `(distance, index)`
+//! so ties come out the same way every time.
+
+use crate::point::Point3;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub struct KdTree {
+    points: Vec<Point3>,
+    /// Point indices arranged so every range `lo..hi` has its splitting point at
+    /// the middle, split on axis `depth % 3`.
+    order: Vec<usize>,
+}
+
+impl KdTree {
+    pub fn new(points: &[Point3]) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        build(points, &mut order, 0);
+        Self {
+            points: points.to_vec(),
+            order,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn points(&self) -> &[Point3] {
+        &self.points
+    }
+
+    /// The `k` points closest to `query` as `(distance, index)`, nearest first.
+    pub fn nearest(&self, query: Point3, k: usize) -> Vec<(u128, usize)> {
+        self.nearest_filtered(query, k, |_, _| true)
+    }
+
+    /// The `k` points closest to point `i`, not counting `i` itself.
+    pub fn nearest_to(&self, i: usize, k: usize) -> Vec<(u128, usize)> {
+        self.nearest_filtered(self.points[i], k, |_, j| j != i)
+    }
+
+    /// The point after `after` in point `i`'s `(distance, index)` order, only
+    /// counting points with an index above `i`.
+    pub fn next_nearest(&self, i: usize, after: Option<(u128, usize)>) -> Option<(u128, usize)> {
+        let found = self.nearest_filtered(self.points[i], 1, |d, j| {
+            j > i && after.is_none_or(|after| (d, j) > after)
+        });
+        found.first().copied()
+    }
+
+    /// Every pair of points as `(distance, i, j)` with `i < j`, in increasing order.
+    pub fn pairs(&self) -> Pairs<'_> {
+        let heap = (0..self.len())
+            .filter_map(|i| {
+                let (d, j) = self.next_nearest(i, None)?;
+                Some(Reverse((d, i, j)))
+            })
+            .collect();
+        Pairs { tree: self, heap }
+    }
+
+    /// Every point within `radius_squared` of `query` (inclusive), nearest first.
+    pub fn within(&self, query: Point3, radius_squared: u128) -> Vec<(u128, usize)> {
+        let mut found = Vec::new();
+        self.search_radius(query, radius_squared, 0, self.order.len(), 0, &mut found);
+        found.sort_unstable();
+        found
+    }
+
+    /// The closest two points as `(distance, i, j)` with `i < j`.
+    pub fn nearest_pair(&self) -> Option<(u128, usize, usize)> {
+        (0..self.len())
+            .filter_map(|i| {
+                let &(d, j) = self.nearest_to(i, 1).first()?;
+                Some((d, i.min(j), i.max(j)))
+            })
+            .min()
+    }
+
+    fn nearest_filtered(
+        &self,
+        query: Point3,
+        k: usize,
+        keep: impl Fn(u128, usize) -> bool,
+    ) -> Vec<(u128, usize)> {
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search_nearest(query, k, &keep, 0, self.order.len(), 0, &mut best);
+        }
+        best.into_sorted_vec()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search_nearest(
+        &self,
+        query: Point3,
+        k: usize,
+        keep: &impl Fn(u128, usize) -> bool,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        best: &mut BinaryHeap<(u128, usize)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let i = self.order[mid];
+        let d = query.squared_euclidean(self.points[i]);
+        if keep(d, i) {
+            best.push((d, i));
+            if best.len() > k {
+                best.pop();
+            }
+        }
+        let axis = depth % 3;
+        let diff = query.axis(axis) - self.points[i].axis(axis);
+        let (near, far) = if diff < 0 {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+        self.search_nearest(query, k, keep, near.0, near.1, depth + 1, best);
+        // The far side can only help if the splitting plane is no further than the
+        // current k-th best; ties matter because a lower index wins them.
+        let plane = u128::from(diff.unsigned_abs()).pow(2);
+        if best.len() < k || best.peek().is_some_and(|&(d, _)| plane <= d) {
+            self.search_nearest(query, k, keep, far.0, far.1, depth + 1, best);
+        }
+    }
+
+    fn search_radius(
+        &self,
+        query: Point3,
+        radius_squared: u128,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        found: &mut Vec<(u128, usize)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let i = self.order[mid];
+        let d = query.squared_euclidean(self.points[i]);
+        if d <= radius_squared {
+            found.push((d, i));
+        }
+        let axis = depth % 3;
+        let diff = query.axis(axis) - self.points[i].axis(axis);
+        let plane = u128::from(diff.unsigned_abs()).pow(2);
+        if diff < 0 || plane <= radius_squared {
+            self.search_radius(query, radius_squared, lo, mid, depth + 1, found);
+        }
+        if diff >= 0 || plane <= radius_squared {
+            self.search_radius(query, radius_squared, mid + 1, hi, depth + 1, found);
+        }
+    }
+}
+
+/// Iterator returned by [`KdTree::pairs`].
+///
+/// Holds one pending pair per point, so memory stays linear however far it is run.
+pub struct Pairs<'a> {
+    tree: &'a KdTree,
+    heap: BinaryHeap<Reverse<(u128, usize, usize)>>,
+}
+
+impl Iterator for Pairs<'_> {
+    type Item = (u128, usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((d, i, j)) = self.heap.pop()?;
+        if let Some((next_d, next_j)) = self.tree.next_nearest(i, Some((d, j))) {
+            self.heap.push(Reverse((next_d, i, next_j)));
+        }
+        Some((d, i, j))
+    }
+}
+
+/// Arranges `order` into a balanced tree, median-splitting by axis `depth % 3`.
+fn build(points: &[Point3], order: &mut [usize], depth: usize) {
+    if order.len() <= 1 {
+        return;
+    }
+    let axis = depth % 3;
+    let mid = order.len() / 2;
+    order.select_nth_unstable_by_key(mid, |&i| points[i].axis(axis));
+    let (left, right) = order.split_at_mut(mid);
+    build(points, left, depth + 1);
+    build(points, &mut right[1..], depth + 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    fn random_points(rng: &mut Rng, n: usize, max: i64) -> Vec<Point3> {
+        (0..n)
+            .map(|_| Point3::new(rng.range(0..=max), rng.range(0..=max), rng.range(0..=max)))
+            .collect()
+    }
+
+    fn brute_force(points: &[Point3], query: Point3) -> Vec<(u128, usize)> {
+        let mut all: Vec<_> = points
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| (query.squared_euclidean(p), i))
+            .collect();
+        all.sort_unstable();
+        all
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = Rng::new(8);
+        // A small coordinate range forces plenty of ties and duplicate points.
+        for max in [5, 1000] {
+            let points = random_points(&mut rng, 200, max);
+            let tree = KdTree::new(&points);
+            for _ in 0..50 {
+                let query = random_points(&mut rng, 1, max)[0];
+                let all = brute_force(&points, query);
+                for k in [1, 3, 17, 250] {
+                    assert_eq!(tree.nearest(query, k), all[..k.min(all.len())]);
+                }
+                let radius = all[20].0;
+                let inside: Vec<_> = all.iter().copied().filter(|&(d, _)| d <= radius).collect();
+                assert_eq!(tree.within(query, radius), inside);
+            }
+        }
+    }
+
+    #[test]
+    fn pairs_in_sorted_order() {
+        let mut rng = Rng::new(42);
+        for max in [3, 1000] {
+            let points = random_points(&mut rng, 60, max);
+            let mut all = Vec::new();
+            for i in 0..points.len() {
+                for j in i + 1..points.len() {
+                    all.push((points[i].squared_euclidean(points[j]), i, j));
+                }
+            }
+            all.sort_unstable();
+            let tree = KdTree::new(&points);
+            assert_eq!(tree.pairs().collect::<Vec<_>>(), all);
+        }
+    }
+
+    #[test]
+    fn nearest_to_skips_itself() {
+        let points = [
+            Point3::new(0, 0, 0),
+            Point3::new(10, 0, 0),
+            Point3::new(0, 3, 4),
+            Point3::new(9, 9, 9),
+        ];
+        let tree = KdTree::new(&points);
+        assert_eq!(tree.nearest_to(0, 2), [(25, 2), (100, 1)]);
+        assert_eq!(tree.nearest_pair(), Some((25, 0, 2)));
+        assert_eq!(KdTree::new(&[]).nearest_pair(), None);
+    }
+}
@@ -0,0 +1,308 @@
+//! Offline analysis of a private leaderboard saved as JSON from the Advent of Code site.
+
+use crate::format_timestamp;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+#[derive(Deserialize)]
+struct RawLeaderboard {
+    event: String,
+    owner_id: u64,
+    members: HashMap<String, RawMember>,
+}
+
+#[derive(Deserialize)]
+struct RawMember {
+    id: u64,
+    name: Option<String>,
+    stars: u32,
+    local_score: u64,
+    last_star_ts: i64,
+    completion_day_level: HashMap<String, HashMap<String, RawStar>>,
+}
+
+#[derive(Deserialize)]
+struct RawStar {
+    get_star_ts: i64,
+}
+
+/// Star timestamps for one member on one day.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DayStars {
+    pub part1: Option<i64>,
+    pub part2: Option<i64>,
+}
+
+impl DayStars {
+    /// Seconds between the two stars of the day, if both were earned.
+    pub fn gap(&self) -> Option<i64> {
+        Some(self.part2? - self.part1?)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    pub name: String,
+    pub stars: u32,
+    pub local_score: u64,
+    pub last_star_ts: i64,
+    pub days: BTreeMap<u8, DayStars>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    /// Members in ranking order: by local score, then by who got their last star first.
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text =
+            fs::read_to_string(path).with_context(|| format!("Reading {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("Parsing {}", path.display()))
+    }
+
+    pub fn parse(json: &str) -> Result<Self> {
+        let raw: RawLeaderboard = serde_json::from_str(json)?;
+        let mut members = Vec::with_capacity(raw.members.len());
+        for member in raw.members.into_values() {
+            let mut days = BTreeMap::new();
+            for (day, parts) in member.completion_day_level {
+                let day: u8 = day.parse().with_context(|| format!("Invalid day: {day}"))?;
+                let stars = DayStars {
+                    part1: parts.get("1").map(|s| s.get_star_ts),
+                    part2: parts.get("2").map(|s| s.get_star_ts),
+                };
+                days.insert(day, stars);
+            }
+            members.push(Member {
+                id: member.id,
+                name: member
+                    .name
+                    .unwrap_or_else(|| format!("(anonymous user #{})", member.id)),
+                stars: member.stars,
+                local_score: member.local_score,
+                last_star_ts: member.last_star_ts,
+                days,
+            });
+        }
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+        Ok(Self {
+            event: raw.event,
+            owner_id: raw.owner_id,
+            members,
+        })
+    }
+
+    /// Highest day any member has a star for.
+    pub fn last_day(&self) -> u8 {
+        self.members
+            .iter()
+            .filter_map(|m| m.days.keys().next_back().copied())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Recomputes local scores from the star timestamps: on every star,
+    /// the first member to earn it gets one point per member, the next one less, and so on.
+    pub fn computed_local_scores(&self) -> HashMap<u64, u64> {
+        let n = self.members.len() as u64;
+        let mut scores: HashMap<u64, u64> = self.members.iter().map(|m| (m.id, 0)).collect();
+        for day in 1..=self.last_day() {
+            for part in [1, 2] {
+                let mut times: Vec<(i64, u64)> = self
+                    .members
+                    .iter()
+                    .filter_map(|m| {
+                        let stars = m.days.get(&day)?;
+                        let ts = if part == 1 { stars.part1 } else { stars.part2 };
+                        Some((ts?, m.id))
+                    })
+                    .collect();
+                times.sort_unstable();
+                for (rank, (_, id)) in times.into_iter().enumerate() {
+                    *scores.get_mut(&id).unwrap() += n - rank as u64;
+                }
+            }
+        }
+        scores
+    }
+
+    /// Members with gold (both parts) and silver (part 1 only) stars for `day`.
+    pub fn completion(&self, day: u8) -> (usize, usize) {
+        let mut gold = 0;
+        let mut silver = 0;
+        for stars in self.members.iter().filter_map(|m| m.days.get(&day)) {
+            match (stars.part1, stars.part2) {
+                (Some(_), Some(_)) => gold += 1,
+                (Some(_), None) => silver += 1,
+                _ => {}
+            }
+        }
+        (gold, silver)
+    }
+
+    pub fn render_ranking(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "Rank  Score  Stars  Last star            Name").unwrap();
+        for (i, m) in self.members.iter().enumerate() {
+            writeln!(
+                out,
+                "{:>4}  {:>5}  {:>5}  {}  {}",
+                i + 1,
+                m.local_score,
+                m.stars,
+                format_timestamp(m.last_star_ts),
+                m.name
+            )
+            .unwrap();
+        }
+        out
+    }
+
+    /// Time from part 1 to part 2 for every member and day.
+    pub fn render_gaps(&self) -> String {
+        let mut out = String::new();
+        let name_width = self.name_width();
+        write!(out, "{:name_width$}", "Part 1 -> 2").unwrap();
+        for day in 1..=self.last_day() {
+            write!(out, " {day:>7}").unwrap();
+        }
+        writeln!(out).unwrap();
+        for m in &self.members {
+            write!(out, "{:name_width$}", m.name).unwrap();
+            for day in 1..=self.last_day() {
+                let cell = match m.days.get(&day) {
+                    Some(stars) if stars.part1.is_some() => {
+                        stars.gap().map_or_else(|| "-".to_string(), format_duration)
+                    }
+                    _ => String::new(),
+                };
+                write!(out, " {cell:>7}").unwrap();
+            }
+            writeln!(out).unwrap();
+        }
+        out
+    }
+
+    /// One bar per day: `*` for each member with both stars, `+` for part 1 only.
+    pub fn render_histogram(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "Day Gold Silver").unwrap();
+        for day in 1..=self.last_day() {
+            let (gold, silver) = self.completion(day);
+            writeln!(
+                out,
+                "{day:>3} {gold:>4} {silver:>6}  {}{}",
+                "*".repeat(gold),
+                "+".repeat(silver)
+            )
+            .unwrap();
+        }
+        out
+    }
+
+    fn name_width(&self) -> usize {
+        self.members
+            .iter()
+            .map(|m| m.name.chars().count())
+            .chain([11])
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Compact duration: `45s`, `12m05s`, `3h07m` or `2d04h`.
+pub fn format_duration(secs: i64) -> String {
+    let (d, h, m, s) = (secs / 86_400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    if d > 0 {
+        format!("{d}d{h:02}h")
+    } else if h > 0 {
+        format!("{h}h{m:02}m")
+    } else if m > 0 {
+        format!("{m}m{s:02}s")
+    } else {
+        format!("{s}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/leaderboard.json");
+
+    #[test]
+    fn parses_members() {
+        let board = Leaderboard::parse(FIXTURE).unwrap();
+        assert_eq!(board.event, "2025");
+        assert_eq!(board.owner_id, 101);
+        let ranking: Vec<(u64, u64)> = board
+            .members
+            .iter()
+            .map(|m| (m.id, m.local_score))
+            .collect();
+        assert_eq!(ranking, [(202, 21), (101, 15), (404, 12), (303, 4)]);
+        assert_eq!(board.members[3].name, "(anonymous user #303)");
+        assert_eq!(board.members[1].days[&1].gap(), Some(278));
+        assert_eq!(board.members[1].days[&3].gap(), None);
+        assert_eq!(board.last_day(), 3);
+    }
+
+    #[test]
+    fn local_scores_match() {
+        let board = Leaderboard::parse(FIXTURE).unwrap();
+        let computed = board.computed_local_scores();
+        for m in &board.members {
+            assert_eq!(computed[&m.id], m.local_score, "{}", m.name);
+        }
+    }
+
+    #[test]
+    fn histogram() {
+        let board = Leaderboard::parse(FIXTURE).unwrap();
+        assert_eq!(board.completion(1), (4, 0));
+        assert_eq!(board.completion(2), (2, 1));
+        assert_eq!(
+            board.render_histogram(),
+            "Day Gold Silver\n  1    4      0  ****\n  2    2      1  **+\n  3    2      1  **+\n"
+        );
+    }
+
+    #[test]
+    fn gaps() {
+        let board = Leaderboard::parse(FIXTURE).unwrap();
+        let gaps = board.render_gaps();
+        let lines: Vec<&str> = gaps.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[1].split_whitespace().collect::<Vec<_>>(),
+            ["Grace", "Hopper", "10m24s", "8m45s", "38m00s"]
+        );
+        assert_eq!(
+            lines[3].split_whitespace().collect::<Vec<_>>(),
+            ["Alan", "Turing", "55s", "5m00s"]
+        );
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(format_duration(45), "45s");
+        assert_eq!(format_duration(725), "12m05s");
+        assert_eq!(format_duration(3 * 3600 + 7 * 60), "3h07m");
+        assert_eq!(format_duration(2 * 86_400 + 4 * 3600), "2d04h");
+    }
+}
@@ -1,9 +1,85 @@
+pub mod answer;
+pub mod automaton;
+pub mod clustering;
+pub mod differential;
+pub mod direction;
+pub mod disjoint_set;
+pub mod generate;
+pub mod graph;
+pub mod grid;
+pub mod kdtree;
+pub mod leaderboard;
+pub mod ocr;
+pub mod params;
+pub mod point;
+pub mod progress;
+pub mod rng;
+pub mod search;
+pub mod sparse_grid;
+
+use answer::Answer;
+use progress::{PROGRESS_FILE, Progress};
+
+pub const YEAR: i32 = 2025;
+pub const DAYS: u8 = 12;
+
 pub fn start_day(day: &str) {
-    println!("Advent of Code 2025 - Day {:0>2}", day);
+    println!("Advent of Code {YEAR} - Day {:0>2}", day);
+}
+
+/// Prints a part's result, and warns if it differs from the answer recorded
+/// for that part in the progress file.
+pub fn print_result(day: &str, part: u8, result: impl Into<Answer>) {
+    let answer = result.into();
+    println!("Result = {answer}");
+    let Ok(day) = day.parse() else {
+        return;
+    };
+    match Progress::load(PROGRESS_FILE) {
+        Ok(progress) => {
+            if progress.check(day, part, &answer) == Some(false) {
+                let recorded = &progress.get(day, part).unwrap().answer;
+                println!("Warning: the recorded answer is {recorded}");
+            }
+        }
+        Err(e) => eprintln!("Could not check the recorded answer: {e:#}"),
+    }
 }
 
 // Additional common functions
 
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+pub fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let y = i64::from(if month <= 2 { year - 1 } else { year });
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = i64::from((month + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Formats a Unix timestamp as `YYYY-MM-DD HH:MM:SS` (UTC).
+pub fn format_timestamp(secs: i64) -> String {
+    let days = secs.div_euclid(86_400);
+    let rem = secs.rem_euclid(86_400);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        rem / 3600,
+        rem / 60 % 60,
+        rem % 60
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -12,4 +88,12 @@ mod tests {
     fn it_works() {
         start_day("00");
     }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        let dec1 = days_from_civil(2025, 12, 1) * 86_400 + 5 * 3600;
+        assert_eq!(format_timestamp(dec1), "2025-12-01 05:00:00");
+    }
 }
@@ -0,0 +1,292 @@
+//! Reads answers drawn as block letters of `#` and `.`.
+//!
+//! Knows the two Advent of Code fonts: letters 6 pixels tall (4 wide, some 5)
+//! and letters 10 pixels tall (6 wide). Letters are split on blank columns.
+
+use crate::answer::Answer;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+const SMALL: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LARGE: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OcrError {
+    /// Nothing is lit.
+    Empty,
+    /// Rows aren't all the same width.
+    Ragged,
+    /// The lit rows are neither 6 nor 10 pixels tall.
+    UnsupportedHeight(usize),
+    /// A letter starting at `column` that isn't in the font, drawn with `#` and `.`.
+    UnknownGlyph { column: usize, glyph: String },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcrError::Empty => write!(f, "No lit pixels to read"),
+            OcrError::Ragged => write!(f, "Rows have different widths"),
+            OcrError::UnsupportedHeight(h) => {
+                write!(f, "Letters are {h} pixels tall; expected 6 or 10")
+            }
+            OcrError::UnknownGlyph { column, glyph } => {
+                write!(f, "Unknown letter at column {column}:\n{glyph}")
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+/// Decodes rows of pixels (`true` = lit) into the letters they spell.
+pub fn recognize(rows: &[Vec<bool>]) -> Result<String, OcrError> {
+    let width = rows.first().map_or(0, Vec::len);
+    if rows.iter().any(|r| r.len() != width) {
+        return Err(OcrError::Ragged);
+    }
+    let lit_rows: Vec<usize> = (0..rows.len())
+        .filter(|&y| rows[y].iter().any(|&p| p))
+        .collect();
+    let (Some(&top), Some(&bottom)) = (lit_rows.first(), lit_rows.last()) else {
+        return Err(OcrError::Empty);
+    };
+    let rows = &rows[top..=bottom];
+    let font = match rows.len() {
+        6 => SMALL,
+        10 => LARGE,
+        h => return Err(OcrError::UnsupportedHeight(h)),
+    };
+
+    let blank = |x: usize| rows.iter().all(|r| !r[x]);
+    let mut text = String::new();
+    let mut x = 0;
+    while x < width {
+        if blank(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && !blank(x) {
+            x += 1;
+        }
+        let glyph = render(rows, start..x);
+        let letter = font
+            .iter()
+            .find(|(_, pattern)| trim_columns(pattern) == glyph)
+            .map(|&(c, _)| c)
+            .ok_or(OcrError::UnknownGlyph {
+                column: start,
+                glyph,
+            })?;
+        text.push(letter);
+    }
+    Ok(text)
+}
+
+/// Like [`recognize`], reading `#` as lit and anything else as dark.
+pub fn recognize_str(drawing: &str) -> Result<String, OcrError> {
+    let lines: Vec<&str> = drawing.lines().collect();
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let rows: Vec<Vec<bool>> = lines
+        .iter()
+        .map(|l| {
+            let mut row: Vec<bool> = l.chars().map(|c| c == '#').collect();
+            row.resize(width, false);
+            row
+        })
+        .collect();
+    recognize(&rows)
+}
+
+fn render(rows: &[Vec<bool>], columns: std::ops::Range<usize>) -> String {
+    let lines: Vec<String> = rows
+        .iter()
+        .map(|r| {
+            r[columns.clone()]
+                .iter()
+                .map(|&p| if p { '#' } else { '.' })
+                .collect()
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// A font pattern without its blank leading and trailing columns.
+fn trim_columns(pattern: &str) -> String {
+    let lines: Vec<&str> = pattern.lines().collect();
+    let lit = |x: usize| lines.iter().any(|l| l.as_bytes()[x] == b'#');
+    let width = lines[0].len();
+    let start = (0..width).find(|&x| lit(x)).unwrap_or(0);
+    let end = (0..width).rfind(|&x| lit(x)).map_or(0, |x| x + 1);
+    let lines: Vec<&str> = lines.iter().map(|l| &l[start..end]).collect();
+    lines.join("\n")
+}
+
+impl Answer {
+    /// An answer spelled out in block letters.
+    pub fn from_ocr(rows: &[Vec<bool>]) -> Result<Answer, OcrError> {
+        recognize(rows).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_font() {
+        let drawing = "\
+.##..###..####.#..#.
+#..#.#..#.#....#..#.
+#..#.###..###..####.
+####.#..#.#....#..#.
+#..#.#..#.#....#..#.
+#..#.###..####.#..#.
+";
+        assert_eq!(recognize_str(drawing).unwrap(), "ABEH");
+    }
+
+    #[test]
+    fn every_letter_round_trips() {
+        for font in [SMALL, LARGE] {
+            let letters: Vec<Vec<&str>> = font.iter().map(|(_, p)| p.lines().collect()).collect();
+            let height = letters[0].len();
+            let drawing: Vec<String> = (0..height)
+                .map(|y| letters.iter().map(|l| l[y]).collect::<Vec<_>>().join(".."))
+                .collect();
+            let expected: String = font.iter().map(|&(c, _)| c).collect();
+            assert_eq!(recognize_str(&drawing.join("\n")).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn blank_rows_around_letters() {
+        let drawing = "\
+...........
+.#......##.
+.#.......#.
+.#.......#.
+.#.......#.
+.#....#..#.
+.####..##..
+...........
+";
+        assert_eq!(recognize_str(drawing).unwrap(), "LJ");
+    }
+
+    #[test]
+    fn reports_unknown_glyph() {
+        let drawing = "####.#\n####.#\n####.#\n####.#\n####.#\n####.#";
+        let err = recognize_str(drawing).unwrap_err();
+        assert_eq!(
+            err,
+            OcrError::UnknownGlyph {
+                column: 0,
+                glyph: "####\n####\n####\n####\n####\n####".to_string()
+            }
+        );
+        assert!(err.to_string().starts_with("Unknown letter at column 0"));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(recognize_str("....\n...."), Err(OcrError::Empty));
+        assert_eq!(
+            recognize_str("#\n#\n#"),
+            Err(OcrError::UnsupportedHeight(3))
+        );
+        assert_eq!(recognize(&[vec![true], vec![]]), Err(OcrError::Ragged));
+    }
+
+    #[test]
+    fn into_answer() {
+        let rows: Vec<Vec<bool>> = SMALL[11]
+            .1
+            .lines()
+            .map(|l| l.chars().map(|c| c == '#').collect())
+            .collect();
+        assert_eq!(Answer::from_ocr(&rows).unwrap(), Answer::from("O"));
+    }
+}
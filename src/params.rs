@@ -0,0 +1,93 @@
+//! Named per-run parameters, so the example and the real input can use different constants.
+
+use anyhow::{Context, Result, bail};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// `key=value` pairs handed to a part alongside its input.
+///
+/// Examples declare their own values with [`Params::with`]; the real input
+/// takes whatever is given on the command line and falls back to the defaults
+/// passed to [`Params::get`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, key: &str, value: impl Display) -> Self {
+        self.values.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Parameters given as `key=value` arguments to the current program.
+    pub fn from_args() -> Result<Self> {
+        Self::parse_args(std::env::args().skip(1))
+    }
+
+    pub fn parse_args<I, S>(args: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut params = Self::new();
+        for arg in args {
+            let arg = arg.as_ref();
+            let Some((key, value)) = arg.split_once('=') else {
+                bail!("Expected key=value, got: {arg}");
+            };
+            params = params.with(key.trim(), value.trim());
+        }
+        Ok(params)
+    }
+
+    /// The value for `key`, or `default` if it was not given.
+    pub fn get<T>(&self, key: &str, default: T) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        match self.values.get(key) {
+            Some(value) => value
+                .parse()
+                .with_context(|| format!("Invalid value for parameter {key}: {value}")),
+            None => Ok(default),
+        }
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_and_overrides() {
+        let example = Params::new().with("pairs", 10);
+        assert_eq!(example.get("pairs", 1000).unwrap(), 10);
+        assert_eq!(Params::new().get("pairs", 1000).unwrap(), 1000);
+
+        let cli = Params::parse_args(["pairs=25", "mode = fast"]).unwrap();
+        assert_eq!(cli.get("pairs", 0).unwrap(), 25);
+        assert_eq!(cli.get("mode", String::new()).unwrap(), "fast");
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert!(Params::parse_args(["pairs"]).is_err());
+        let params = Params::new().with("pairs", "ten");
+        assert!(params.get("pairs", 0usize).is_err());
+    }
+}
@@ -0,0 +1,314 @@
+//! 2D and 3D integer points/vectors.
+
+use anyhow::{Context, Result, bail};
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Integer types usable as point coordinates.
+pub trait Coord:
+    Copy + Ord + Default + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// `|self - other|`, widened so it cannot overflow.
+    fn distance(self, other: Self) -> u64;
+}
+
+macro_rules! coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                fn distance(self, other: Self) -> u64 {
+                    self.abs_diff(other) as u64
+                }
+            }
+        )*
+    };
+}
+
+coord!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T = i64> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T = i64> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coord> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    pub fn chebyshev(self, other: Self) -> u64 {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+
+    pub fn squared_euclidean(self, other: Self) -> u128 {
+        let dx = u128::from(self.x.distance(other.x));
+        let dy = u128::from(self.y.distance(other.y));
+        dx * dx + dy * dy
+    }
+
+    /// Component-wise minimum and maximum, unlike the lexicographic `Ord::min` and `Ord::max`.
+    pub fn component_min(self, other: Self) -> Self {
+        Self::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    pub fn component_max(self, other: Self) -> Self {
+        Self::new(self.x.max(other.x), self.y.max(other.y))
+    }
+}
+
+impl<T: Coord> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.distance(other.x) + self.y.distance(other.y) + self.z.distance(other.z)
+    }
+
+    pub fn chebyshev(self, other: Self) -> u64 {
+        self.x
+            .distance(other.x)
+            .max(self.y.distance(other.y))
+            .max(self.z.distance(other.z))
+    }
+
+    pub fn squared_euclidean(self, other: Self) -> u128 {
+        let dx = u128::from(self.x.distance(other.x));
+        let dy = u128::from(self.y.distance(other.y));
+        let dz = u128::from(self.z.distance(other.z));
+        dx * dx + dy * dy + dz * dz
+    }
+
+    /// Coordinate along axis 0 (x), 1 (y) or 2 (z).
+    pub fn axis(self, axis: usize) -> T {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            _ => panic!("axis {axis} out of range"),
+        }
+    }
+}
+
+impl<T: TryInto<usize> + Copy> Point2<T> {
+    /// The `(x, y)` grid index of this point, if both coordinates are non-negative.
+    pub fn to_index(self) -> Option<(usize, usize)> {
+        Some((self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+}
+
+impl<T: TryFrom<usize>> Point2<T> {
+    pub fn from_index((x, y): (usize, usize)) -> Option<Self> {
+        Some(Self {
+            x: x.try_into().ok()?,
+            y: y.try_into().ok()?,
+        })
+    }
+}
+
+macro_rules! ops {
+    ($p:ident { $($f:ident),* }) => {
+        impl<T: Coord> Add for $p<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($f: self.$f + rhs.$f),* }
+            }
+        }
+
+        impl<T: Coord> Sub for $p<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($f: self.$f - rhs.$f),* }
+            }
+        }
+
+        impl<T: Coord> Mul<T> for $p<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                Self { $($f: self.$f * rhs),* }
+            }
+        }
+
+        impl<T: Coord + Neg<Output = T>> Neg for $p<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($f: -self.$f),* }
+            }
+        }
+
+        impl<T: Coord> AddAssign for $p<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Coord> SubAssign for $p<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+ops!(Point2 { x, y });
+ops!(Point3 { x, y, z });
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(p: Point2<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// Parses comma-separated coordinates, allowing spaces around each one.
+fn parse_coords<T, const N: usize>(s: &str) -> Result<[T; N]>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let parts: Vec<&str> = s.trim().split(',').map(str::trim).collect();
+    if parts.len() != N {
+        bail!("Expected {N} coordinates, got {}: {s}", parts.len());
+    }
+    let mut coords = Vec::with_capacity(N);
+    for part in parts {
+        coords.push(
+            part.parse()
+                .with_context(|| format!("Invalid coordinate {part:?} in {s}"))?,
+        );
+    }
+    Ok(coords.try_into().ok().unwrap())
+}
+
+impl<T> FromStr for Point2<T>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let [x, y] = parse_coords(s)?;
+        Ok(Self { x, y })
+    }
+}
+
+impl<T> FromStr for Point3<T>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let [x, y, z] = parse_coords(s)?;
+        Ok(Self { x, y, z })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+        assert_eq!(a + b, Point2::new(5, 0));
+        assert_eq!(a - b, Point2::new(-3, 4));
+        assert_eq!(a * 3, Point2::new(3, 6));
+        assert_eq!(-a, Point2::new(-1, -2));
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        let p = Point3::new(1, 2, 3) + Point3::new(1, 1, 1) * 2;
+        assert_eq!(p, Point3::new(3, 4, 5));
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.squared_euclidean(b), 25);
+        let p = Point3::new(162, 817, 812);
+        let q = Point3::new(425, 690, 689);
+        assert_eq!(p.squared_euclidean(q), 263 * 263 + 127 * 127 + 123 * 123);
+        assert_eq!(p.manhattan(q), 263 + 127 + 123);
+        assert_eq!(p.chebyshev(q), 263);
+        let far = Point2::new(i64::MIN, 0).squared_euclidean(Point2::new(i64::MAX, 0));
+        assert_eq!(far, u128::from(u64::MAX) * u128::from(u64::MAX));
+    }
+
+    #[test]
+    fn component_bounds() {
+        let a = Point2::new(1, 5);
+        let b = Point2::new(3, 2);
+        assert_eq!(a.component_min(b), Point2::new(1, 2));
+        assert_eq!(a.component_max(b), Point2::new(3, 5));
+        // The derived ordering compares x first, then y.
+        assert_eq!(a.min(b), a);
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!("7, 1".parse::<Point2<i64>>().unwrap(), Point2::new(7, 1));
+        assert_eq!(
+            "162,817,812".parse::<Point3<i32>>().unwrap(),
+            Point3::new(162, 817, 812)
+        );
+        assert!("1,2,3".parse::<Point2<i64>>().is_err());
+        assert!("1,x".parse::<Point2<i64>>().is_err());
+        assert_eq!(Point3::new(1, 2, 3).to_string(), "1,2,3");
+    }
+
+    #[test]
+    fn grid_indices() {
+        assert_eq!(Point2::new(3i64, 4).to_index(), Some((3, 4)));
+        assert_eq!(Point2::new(-1i64, 4).to_index(), None);
+        assert_eq!(Point2::<i32>::from_index((2, 5)), Some(Point2::new(2, 5)));
+        assert_eq!(Point2::<u8>::from_index((300, 5)), None);
+    }
+}
@@ -0,0 +1,291 @@
+//! Local record of solved puzzle parts, rendered as an advent calendar.
+
+use crate::answer::Answer;
+use crate::{DAYS, YEAR, days_from_civil, format_timestamp};
+use anyhow::{Context, Result, bail};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const PROGRESS_FILE: &str = "progress.txt";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solve {
+    pub answer: Answer,
+    pub solved_at: i64,
+}
+
+/// Solved parts keyed by `(day, part)`.
+///
+/// Stored as one tab-separated `day part timestamp answer` line per part, with
+/// backslashes and line breaks in the answer escaped as `\\` and `\n`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Progress {
+    solves: BTreeMap<(u8, u8), Solve>,
+}
+
+impl Progress {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).with_context(|| format!("Reading {}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Reading {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_text()).with_context(|| format!("Writing {}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut solves = BTreeMap::new();
+        for line in text.lines().filter(|l| !l.trim().is_empty()) {
+            let mut fields = line.splitn(4, '\t');
+            let (Some(day), Some(part), Some(ts), Some(answer)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                bail!("Invalid progress line: {line}");
+            };
+            let (day, part) = (day.parse()?, part.parse()?);
+            if !(1..=DAYS).contains(&day) || !(1..=2).contains(&part) {
+                bail!("Invalid day or part in progress line: {line}");
+            }
+            let Ok(answer) = unescape(answer)?.parse();
+            let solve = Solve {
+                answer,
+                solved_at: ts.parse()?,
+            };
+            solves.insert((day, part), solve);
+        }
+        Ok(Self { solves })
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for (&(day, part), solve) in &self.solves {
+            let answer = escape(&solve.answer.to_string());
+            writeln!(out, "{day}\t{part}\t{}\t{answer}", solve.solved_at).unwrap();
+        }
+        out
+    }
+
+    /// Records a solved part with the current time.
+    /// Returns `false` if the part was already recorded; the original entry is kept.
+    pub fn record(&mut self, day: u8, part: u8, answer: &Answer) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX));
+        self.record_at(day, part, answer, now)
+    }
+
+    pub fn record_at(&mut self, day: u8, part: u8, answer: &Answer, solved_at: i64) -> bool {
+        if self.solves.contains_key(&(day, part)) {
+            return false;
+        }
+        let solve = Solve {
+            answer: answer.clone(),
+            solved_at,
+        };
+        self.solves.insert((day, part), solve);
+        true
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Solve> {
+        self.solves.get(&(day, part))
+    }
+
+    /// Whether `answer` matches the recorded one, or `None` if the part is unsolved.
+    pub fn check(&self, day: u8, part: u8, answer: &Answer) -> Option<bool> {
+        self.get(day, part).map(|solve| solve.answer == *answer)
+    }
+
+    pub fn stars(&self, day: u8) -> usize {
+        self.solves.range((day, 0)..=(day, u8::MAX)).count()
+    }
+
+    pub fn total_stars(&self) -> usize {
+        self.solves.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u8, u8, &Solve)> {
+        self.solves
+            .iter()
+            .map(|(&(day, part), solve)| (day, part, solve))
+    }
+}
+
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(field: &str) -> Result<String> {
+    let mut out = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => out.push('\\'),
+            Some('n') => out.push('\n'),
+            _ => bail!("Invalid escape in answer: {field}"),
+        }
+    }
+    Ok(out)
+}
+
+/// Days whose `src/bin/NN.rs` still has the template's Part 2 region commented out.
+pub fn pending_part2(bin_dir: impl AsRef<Path>) -> Vec<u8> {
+    let mut days = Vec::new();
+    for day in 1..=DAYS {
+        let path = bin_dir.as_ref().join(format!("{day:02}.rs"));
+        let Ok(source) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if part2_commented_out(&source) {
+            days.push(day);
+        }
+    }
+    days
+}
+
+fn part2_commented_out(source: &str) -> bool {
+    let mut lines = source.lines().map(str::trim);
+    if !lines.any(|l| l == "//region Part 2") {
+        return false;
+    }
+    lines
+        .find(|l| !l.is_empty())
+        .is_some_and(|l| l.starts_with("//") && l != "//endregion")
+}
+
+/// Renders the year as a Monday-first calendar with the stars earned on each day.
+pub fn render_calendar(progress: &Progress, pending_part2: &[u8]) -> String {
+    let mut out = String::new();
+    let total = usize::from(DAYS) * 2;
+    writeln!(
+        out,
+        "Advent of Code {YEAR}  [{}/{total} *]",
+        progress.total_stars()
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "Mo   Tu   We   Th   Fr   Sa   Su").unwrap();
+    // 1970-01-01 was a Thursday.
+    let offset = (days_from_civil(YEAR, 12, 1) + 3).rem_euclid(7) as usize;
+    let mut line = "     ".repeat(offset);
+    for day in 1..=DAYS {
+        let stars = match progress.stars(day) {
+            0 => "..",
+            1 => "*.",
+            _ => "**",
+        };
+        write!(line, "{day:>2}{stars} ").unwrap();
+        if (offset + usize::from(day)).is_multiple_of(7) {
+            writeln!(out, "{}", line.trim_end()).unwrap();
+            line.clear();
+        }
+    }
+    if !line.is_empty() {
+        writeln!(out, "{}", line.trim_end()).unwrap();
+    }
+    if !pending_part2.is_empty() {
+        let days: Vec<String> = pending_part2.iter().map(|d| format!("{d:02}")).collect();
+        writeln!(out).unwrap();
+        writeln!(out, "Part 2 still commented out: {}", days.join(", ")).unwrap();
+    }
+    out
+}
+
+/// One line per solved part: day, part, solve time and answer.
+pub fn render_solves(progress: &Progress) -> String {
+    let mut out = String::new();
+    for (day, part, solve) in progress.iter() {
+        let when = format_timestamp(solve.solved_at);
+        writeln!(out, "Day {day:02} part {part}  {when}  {}", solve.answer).unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut progress = Progress::default();
+        assert!(progress.record_at(1, 1, &"1092".parse().unwrap(), 1_764_565_200));
+        assert!(progress.record_at(1, 2, &"6616".parse().unwrap(), 1_764_566_000));
+        assert!(!progress.record_at(1, 2, &"0".parse().unwrap(), 1_764_567_000));
+        assert!(progress.record_at(3, 1, &"hello world".parse().unwrap(), 1_764_737_000));
+        let parsed = Progress::parse(&progress.to_text()).unwrap();
+        assert_eq!(parsed, progress);
+        assert_eq!(parsed.stars(1), 2);
+        assert_eq!(parsed.stars(2), 0);
+        assert_eq!(parsed.get(1, 2).unwrap().answer, Answer::from(6616));
+        assert_eq!(parsed.check(1, 2, &Answer::from(6616u64)), Some(true));
+        assert_eq!(parsed.check(1, 1, &Answer::from(6616u64)), Some(false));
+        assert_eq!(parsed.check(2, 1, &Answer::from(6616u64)), None);
+        assert_eq!(
+            parsed.get(3, 1).unwrap().answer,
+            Answer::from("hello world")
+        );
+    }
+
+    #[test]
+    fn multi_line_text_answers() {
+        let mut progress = Progress::default();
+        let answer = Answer::from("#..#\n#\\.#\n");
+        progress.record_at(10, 1, &answer, 0);
+        let text = progress.to_text();
+        assert_eq!(text, "10\t1\t0\t#..#\\n#\\\\.#\\n\n");
+        assert_eq!(
+            Progress::parse(&text).unwrap().get(10, 1).unwrap().answer,
+            answer
+        );
+        assert!(Progress::parse("1\t1\t0\tbad\\t\n").is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_days_and_parts() {
+        assert!(Progress::parse("99\t1\t0\t5\n").is_err());
+        assert!(Progress::parse("0\t1\t0\t5\n").is_err());
+        assert!(Progress::parse("1\t7\t0\t5\n").is_err());
+        assert!(Progress::parse("12\t2\t0\t5\n").is_ok());
+    }
+
+    #[test]
+    fn calendar() {
+        let mut progress = Progress::default();
+        progress.record_at(1, 1, &"1".parse().unwrap(), 0);
+        progress.record_at(1, 2, &"2".parse().unwrap(), 0);
+        progress.record_at(8, 1, &"3".parse().unwrap(), 0);
+        let calendar = render_calendar(&progress, &[8]);
+        assert_eq!(
+            calendar,
+            "\
+Advent of Code 2025  [3/24 *]
+
+Mo   Tu   We   Th   Fr   Sa   Su
+ 1**  2..  3..  4..  5..  6..  7..
+ 8*.  9.. 10.. 11.. 12..
+
+Part 2 still commented out: 08
+"
+        );
+    }
+
+    #[test]
+    fn detects_commented_part2() {
+        let template = include_str!("bin/NN.rs");
+        assert!(part2_commented_out(template));
+        assert!(!part2_commented_out(include_str!("bin/01.rs")));
+        assert!(!part2_commented_out(include_str!("bin/12.rs")));
+    }
+}
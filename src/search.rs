@@ -0,0 +1,462 @@
+//! Shortest paths over any state space: breadth-first search, Dijkstra, A*
+//! and 0-1 BFS.
+//!
+//! A search records, for every state it reaches, the cheapest cost found, the
+//! state it came from and how many cheapest paths lead there. Path counts are
+//! exact when every move costs more than zero.
+
+use crate::direction::Direction;
+use crate::grid::Grid;
+use crate::point::Point2;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A node of a search space.
+pub trait State: Clone + Eq + Hash {
+    /// States one move away, each with the cost of that move.
+    fn successors(&self) -> Vec<(Self, u64)>;
+
+    /// A lower bound on the cost to the nearest goal, used by [`astar`].
+    /// It must never overestimate, and should not drop by more than the cost
+    /// of any one move, or A* may return a longer path.
+    fn heuristic(&self) -> u64 {
+        0
+    }
+}
+
+/// How much work a search did.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// States taken off the frontier and expanded.
+    pub expanded: usize,
+    /// Distinct states seen, expanded or not.
+    pub discovered: usize,
+    /// Largest number of entries waiting on the frontier at once.
+    pub max_frontier: usize,
+}
+
+/// Everything a search learned, up to the first goal it expanded.
+#[derive(Clone, Debug)]
+pub struct Outcome<S> {
+    states: Vec<S>,
+    ids: HashMap<S, usize>,
+    cost: Vec<u64>,
+    parent: Vec<Option<usize>>,
+    count: Vec<u128>,
+    expanded: Vec<bool>,
+    goal: Option<usize>,
+    stats: Stats,
+}
+
+impl<S: State> Outcome<S> {
+    /// The goal that was reached, if any.
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.map(|id| &self.states[id])
+    }
+
+    /// The cost of the cheapest path to the goal.
+    pub fn cost(&self) -> Option<u64> {
+        self.goal.map(|id| self.cost[id])
+    }
+
+    /// A cheapest path from the start to the goal, both included.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.goal.map(|id| self.path_from(id))
+    }
+
+    /// How many different cheapest paths lead to the goal; saturates at `u128::MAX`.
+    pub fn path_count(&self) -> Option<u128> {
+        self.goal.map(|id| self.count[id])
+    }
+
+    /// The cheapest cost to `state`, if the search expanded it before stopping.
+    pub fn cost_to(&self, state: &S) -> Option<u64> {
+        self.settled(state).map(|id| self.cost[id])
+    }
+
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.settled(state).map(|id| self.path_from(id))
+    }
+
+    pub fn path_count_to(&self, state: &S) -> Option<u128> {
+        self.settled(state).map(|id| self.count[id])
+    }
+
+    /// Every expanded state with its cheapest cost, cheapest first.
+    pub fn costs(&self) -> impl Iterator<Item = (&S, u64)> {
+        let mut order: Vec<usize> = (0..self.states.len())
+            .filter(|&id| self.expanded[id])
+            .collect();
+        order.sort_by_key(|&id| self.cost[id]);
+        order
+            .into_iter()
+            .map(|id| (&self.states[id], self.cost[id]))
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    fn settled(&self, state: &S) -> Option<usize> {
+        self.ids.get(state).copied().filter(|&id| self.expanded[id])
+    }
+
+    fn path_from(&self, mut id: usize) -> Vec<S> {
+        let mut path = vec![self.states[id].clone()];
+        while let Some(parent) = self.parent[id] {
+            path.push(self.states[parent].clone());
+            id = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// The order states wait to be expanded in.
+trait Frontier {
+    /// Queues state `id`, reached at cost `g` by a move costing `step`, with
+    /// estimated total `f`.
+    fn push(&mut self, id: usize, g: u64, f: u64, step: u64);
+    fn pop(&mut self) -> Option<(usize, u64)>;
+    fn len(&self) -> usize;
+}
+
+/// First in, first out.
+#[derive(Default)]
+struct Fifo(VecDeque<(usize, u64)>);
+
+impl Frontier for Fifo {
+    fn push(&mut self, id: usize, g: u64, _f: u64, _step: u64) {
+        self.0.push_back((id, g));
+    }
+
+    fn pop(&mut self) -> Option<(usize, u64)> {
+        self.0.pop_front()
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Free moves go to the front, unit moves to the back.
+#[derive(Default)]
+struct ZeroOne(VecDeque<(usize, u64)>);
+
+impl Frontier for ZeroOne {
+    fn push(&mut self, id: usize, g: u64, _f: u64, step: u64) {
+        match step {
+            0 => self.0.push_front((id, g)),
+            1 => self.0.push_back((id, g)),
+            _ => panic!("0-1 BFS needs every move to cost 0 or 1, not {step}"),
+        }
+    }
+
+    fn pop(&mut self) -> Option<(usize, u64)> {
+        self.0.pop_front()
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Lowest estimate first, and among equal estimates the lowest cost so far.
+#[derive(Default)]
+struct Heap(BinaryHeap<Reverse<(u64, u64, usize)>>);
+
+impl Frontier for Heap {
+    fn push(&mut self, id: usize, g: u64, f: u64, _step: u64) {
+        self.0.push(Reverse((f, g, id)));
+    }
+
+    fn pop(&mut self) -> Option<(usize, u64)> {
+        self.0.pop().map(|Reverse((_, g, id))| (id, g))
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Fewest moves from `start` to a state where `is_goal` holds, ignoring move costs.
+pub fn bfs<S: State>(start: S, is_goal: impl FnMut(&S) -> bool) -> Outcome<S> {
+    run(start, is_goal, Fifo::default(), true, false)
+}
+
+/// Cheapest path from `start` to a goal.
+pub fn dijkstra<S: State>(start: S, is_goal: impl FnMut(&S) -> bool) -> Outcome<S> {
+    run(start, is_goal, Heap::default(), false, false)
+}
+
+/// Cheapest path from `start` to a goal, guided by [`State::heuristic`].
+pub fn astar<S: State>(start: S, is_goal: impl FnMut(&S) -> bool) -> Outcome<S> {
+    run(start, is_goal, Heap::default(), false, true)
+}
+
+/// Cheapest path when every move costs 0 or 1; panics on any other cost.
+pub fn zero_one_bfs<S: State>(start: S, is_goal: impl FnMut(&S) -> bool) -> Outcome<S> {
+    run(start, is_goal, ZeroOne::default(), false, false)
+}
+
+fn run<S: State>(
+    start: S,
+    mut is_goal: impl FnMut(&S) -> bool,
+    mut frontier: impl Frontier,
+    unit_costs: bool,
+    guided: bool,
+) -> Outcome<S> {
+    let mut outcome = Outcome {
+        states: vec![start.clone()],
+        ids: HashMap::from([(start, 0)]),
+        cost: vec![0],
+        parent: vec![None],
+        count: vec![1],
+        expanded: vec![false],
+        goal: None,
+        stats: Stats::default(),
+    };
+    frontier.push(0, 0, 0, 0);
+    outcome.stats.max_frontier = 1;
+
+    while let Some((u, g)) = frontier.pop() {
+        if outcome.expanded[u] || g > outcome.cost[u] {
+            continue;
+        }
+        outcome.expanded[u] = true;
+        outcome.stats.expanded += 1;
+        if is_goal(&outcome.states[u]) {
+            outcome.goal = Some(u);
+            break;
+        }
+        for (next, step) in outcome.states[u].successors() {
+            let step = if unit_costs { 1 } else { step };
+            let g_next = g.saturating_add(step);
+            let v = match outcome.ids.get(&next) {
+                Some(&v) => v,
+                None => {
+                    let v = outcome.states.len();
+                    outcome.ids.insert(next.clone(), v);
+                    outcome.states.push(next);
+                    outcome.cost.push(u64::MAX);
+                    outcome.parent.push(None);
+                    outcome.count.push(0);
+                    outcome.expanded.push(false);
+                    v
+                }
+            };
+            if g_next < outcome.cost[v] {
+                outcome.cost[v] = g_next;
+                outcome.parent[v] = Some(u);
+                outcome.count[v] = outcome.count[u];
+                let h = if guided {
+                    outcome.states[v].heuristic()
+                } else {
+                    0
+                };
+                frontier.push(v, g_next, g_next.saturating_add(h), step);
+            } else if g_next == outcome.cost[v] && !outcome.expanded[v] {
+                outcome.count[v] = outcome.count[v].saturating_add(outcome.count[u]);
+            }
+        }
+        outcome.stats.max_frontier = outcome.stats.max_frontier.max(frontier.len());
+    }
+    outcome.stats.discovered = outcome.states.len();
+    outcome
+}
+
+/// A position on a [`Grid`], moving one orthogonal step at a time.
+///
+/// `cost` gives the price of entering a cell, or `None` for a wall. With a
+/// target set, the heuristic is the Manhattan distance to it, which suits A*
+/// as long as no cell is free to enter.
+pub struct GridWalk<'a, T> {
+    grid: &'a Grid<T>,
+    cost: fn(&T) -> Option<u64>,
+    target: Option<Point2<usize>>,
+    pub position: Point2<usize>,
+}
+
+impl<'a, T> GridWalk<'a, T> {
+    pub fn new(grid: &'a Grid<T>, cost: fn(&T) -> Option<u64>, position: Point2<usize>) -> Self {
+        Self {
+            grid,
+            cost,
+            target: None,
+            position,
+        }
+    }
+
+    /// Aims the heuristic at `target`.
+    pub fn toward(mut self, target: Point2<usize>) -> Self {
+        self.target = Some(target);
+        self
+    }
+}
+
+impl<T> Clone for GridWalk<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridWalk<'_, T> {}
+
+impl<T> PartialEq for GridWalk<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.position == other.position
+    }
+}
+
+impl<T> Eq for GridWalk<'_, T> {}
+
+impl<T> Hash for GridWalk<'_, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.position.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for GridWalk<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GridWalk({}, {})", self.position.x, self.position.y)
+    }
+}
+
+impl<T> State for GridWalk<'_, T> {
+    fn successors(&self) -> Vec<(Self, u64)> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(|dir| {
+                let position = self.grid.step(self.position, dir)?;
+                let cost = (self.cost)(&self.grid[position])?;
+                Some((Self { position, ..*self }, cost))
+            })
+            .collect()
+    }
+
+    fn heuristic(&self) -> u64 {
+        self.target
+            .map_or(0, |target| self.position.manhattan(target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A number line where each step is +1 or +3, the latter costing 2.
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    struct Line(u32);
+
+    impl State for Line {
+        fn successors(&self) -> Vec<(Self, u64)> {
+            if self.0 >= 20 {
+                return Vec::new();
+            }
+            vec![(Line(self.0 + 1), 1), (Line(self.0 + 3), 2)]
+        }
+    }
+
+    #[test]
+    fn costs_and_counts() {
+        let moves = bfs(Line(0), |s| s.0 == 9);
+        assert_eq!(moves.cost(), Some(3));
+        assert_eq!(moves.path_count(), Some(1));
+        assert_eq!(moves.path().unwrap(), [Line(0), Line(3), Line(6), Line(9)]);
+
+        // Every jump saves a step, so only three jumps cost 6; 4 is 1+3 or 3+1.
+        let cheapest = dijkstra(Line(0), |s| s.0 == 9);
+        assert_eq!(cheapest.cost(), Some(6));
+        assert_eq!(cheapest.path_count(), Some(1));
+        assert_eq!(cheapest.cost_to(&Line(4)), Some(3));
+        assert_eq!(cheapest.path_count_to(&Line(4)), Some(2));
+        assert_eq!(
+            cheapest.path_to(&Line(2)).unwrap(),
+            [Line(0), Line(1), Line(2)]
+        );
+
+        let unreachable = dijkstra(Line(0), |s| s.0 == 100);
+        assert_eq!(unreachable.goal(), None);
+        assert_eq!(unreachable.stats().expanded, 23);
+        assert_eq!(unreachable.cost_to(&Line(22)), Some(15));
+    }
+
+    /// Moving right along a row is free, moving down costs 1.
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    struct Stairs(u32, u32);
+
+    impl State for Stairs {
+        fn successors(&self) -> Vec<(Self, u64)> {
+            let Stairs(x, y) = *self;
+            let mut next = Vec::new();
+            if x < 5 {
+                next.push((Stairs(x + 1, y), 0));
+            }
+            if y < 5 {
+                next.push((Stairs(x, y + 1), 1));
+            }
+            next
+        }
+    }
+
+    #[test]
+    fn zero_one_matches_dijkstra() {
+        let goal = |s: &Stairs| *s == Stairs(5, 5);
+        let fast = zero_one_bfs(Stairs(0, 0), goal);
+        let slow = dijkstra(Stairs(0, 0), goal);
+        assert_eq!(fast.cost(), Some(5));
+        assert_eq!(fast.cost(), slow.cost());
+        for (state, cost) in slow.costs() {
+            assert_eq!(fast.cost_to(state), Some(cost));
+        }
+    }
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E
+";
+
+    #[test]
+    fn grid_maze() {
+        let grid = Grid::parse(MAZE).unwrap();
+        let start = Point2::from(grid.find(&'S').unwrap());
+        let end = Point2::from(grid.find(&'E').unwrap());
+        let open = |c: &char| (*c != '#').then_some(1);
+        let walk = GridWalk::new(&grid, open, start);
+
+        let plain = bfs(walk, |w| w.position == end);
+        assert_eq!(plain.cost(), Some(15));
+        let path = plain.path().unwrap();
+        assert_eq!(path.len(), 16);
+        assert!(
+            path.windows(2)
+                .all(|w| w[0].position.manhattan(w[1].position) == 1)
+        );
+        assert!(path.iter().all(|w| grid[w.position] != '#'));
+
+        let guided = astar(walk.toward(end), |w| w.position == end);
+        assert_eq!(guided.cost(), Some(15));
+        assert!(guided.stats().expanded <= plain.stats().expanded);
+
+        // Corner to corner of an open 3x3 room: choose 2 of the 4 moves to go down.
+        let room = Grid::parse("...\n...\n...\n").unwrap();
+        let corner = Point2::new(2, 2);
+        let walk = GridWalk::new(&room, open, Point2::new(0, 0));
+        assert_eq!(bfs(walk, |w| w.position == corner).path_count(), Some(6));
+        let guided = astar(walk.toward(corner), |w| w.position == corner);
+        assert_eq!(guided.path_count(), Some(6));
+        assert_eq!(guided.stats().expanded, 9);
+
+        let walled = Grid::parse("S#E\n").unwrap();
+        let walk = GridWalk::new(&walled, open, Point2::new(0, 0));
+        assert_eq!(
+            dijkstra(walk, |w| w.position == Point2::new(2, 0)).goal(),
+            None
+        );
+    }
+}
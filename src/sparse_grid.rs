@@ -0,0 +1,215 @@
+//! Unbounded grid that only stores occupied cells.
+//!
+//! Uses the same `(x, y)` orientation as [`Grid`], but coordinates may be negative or huge.
+
+use crate::grid::Grid;
+use crate::point::Point2;
+use std::collections::HashMap;
+use std::collections::hash_map;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point2, T>,
+    /// Inclusive corners of the occupied area, `None` when empty.
+    bounds: Option<(Point2, Point2)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Stores `value` at `p`, returning what was there before.
+    pub fn insert(&mut self, p: Point2, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (p, p),
+            Some((min, max)) => (min.component_min(p), max.component_max(p)),
+        });
+        self.cells.insert(p, value)
+    }
+
+    pub fn remove(&mut self, p: Point2) -> Option<T> {
+        let value = self.cells.remove(&p)?;
+        if let Some((min, max)) = self.bounds
+            && (p.x == min.x || p.x == max.x || p.y == min.y || p.y == max.y)
+        {
+            self.bounds = Self::bounding_box(self.cells.keys().copied());
+        }
+        Some(value)
+    }
+
+    pub fn get(&self, p: Point2) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point2) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn contains(&self, p: Point2) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    /// Inclusive top-left and bottom-right corners of the occupied cells.
+    pub fn bounds(&self) -> Option<(Point2, Point2)> {
+        self.bounds
+    }
+
+    /// Occupied cells in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.cells.iter().map(|(&p, v)| (p, v))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Point2> + '_ {
+        self.cells.keys().copied()
+    }
+
+    fn bounding_box(points: impl Iterator<Item = Point2>) -> Option<(Point2, Point2)> {
+        points.fold(None, |acc, p| match acc {
+            None => Some((p, p)),
+            Some((min, max)) => Some((min.component_min(p), max.component_max(p))),
+        })
+    }
+
+    /// Draws the inclusive rectangle `min..=max`, one character per cell.
+    pub fn render_viewport(
+        &self,
+        min: Point2,
+        max: Point2,
+        mut cell: impl FnMut(Option<&T>) -> char,
+    ) -> String {
+        let mut out = String::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                out.push(cell(self.get(Point2::new(x, y))));
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Draws the bounding box, one character per cell.
+    pub fn render(&self, cell: impl FnMut(Option<&T>) -> char) -> String {
+        match self.bounds {
+            Some((min, max)) => self.render_viewport(min, max, cell),
+            None => String::new(),
+        }
+    }
+
+    /// The cells of `grid` for which `keep` holds, at the same coordinates.
+    pub fn from_grid(grid: &Grid<T>, mut keep: impl FnMut(&T) -> bool) -> Self
+    where
+        T: Clone,
+    {
+        grid.iter()
+            .filter(|(_, v)| keep(v))
+            .map(|((x, y), v)| (Point2::new(x as i64, y as i64), v.clone()))
+            .collect()
+    }
+
+    /// A dense copy of the bounding box, empty cells set to `fill`, with the
+    /// point that became the grid's `(0, 0)`.
+    pub fn to_grid(&self, fill: T) -> (Grid<T>, Point2)
+    where
+        T: Clone,
+    {
+        let Some((min, max)) = self.bounds else {
+            return (Grid::new(0, 0, fill), Point2::default());
+        };
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+        let mut grid = Grid::new(width, height, fill);
+        for (p, v) in self.iter() {
+            grid[((p.x - min.x) as usize, (p.y - min.y) as usize)] = v.clone();
+        }
+        (grid, min)
+    }
+}
+
+impl<T> FromIterator<(Point2, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point2, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Point2, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point2, T)>>(&mut self, iter: I) {
+        for (p, v) in iter {
+            self.insert(p, v);
+        }
+    }
+}
+
+impl<T> IntoIterator for SparseGrid<T> {
+    type Item = (Point2, T);
+    type IntoIter = hash_map::IntoIter<Point2, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_follow_inserts_and_removes() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.insert(Point2::new(3, -2), 'a');
+        grid.insert(Point2::new(-5, 7), 'b');
+        grid.insert(Point2::new(0, 0), 'c');
+        assert_eq!(
+            grid.bounds(),
+            Some((Point2::new(-5, -2), Point2::new(3, 7)))
+        );
+        assert_eq!(grid.remove(Point2::new(-5, 7)), Some('b'));
+        assert_eq!(grid.bounds(), Some((Point2::new(0, -2), Point2::new(3, 0))));
+        assert_eq!(grid.remove(Point2::new(9, 9)), None);
+        grid.remove(Point2::new(0, 0));
+        grid.remove(Point2::new(3, -2));
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn render_and_convert() {
+        let dense = Grid::parse("#..\n.#.\n..#\n").unwrap();
+        let sparse = SparseGrid::from_grid(&dense, |&c| c == '#');
+        assert_eq!(sparse.len(), 3);
+        let render = sparse.render(|c| c.copied().unwrap_or('.'));
+        assert_eq!(render, dense.to_string());
+        let viewport = sparse.render_viewport(Point2::new(-1, 1), Point2::new(1, 2), |c| {
+            if c.is_some() { '#' } else { ' ' }
+        });
+        assert_eq!(viewport, "  #\n   \n");
+
+        let shifted: SparseGrid<char> = sparse
+            .into_iter()
+            .map(|(p, c)| (p + Point2::new(1_000_000, -40), c))
+            .collect();
+        let (back, origin) = shifted.to_grid('.');
+        assert_eq!(back, dense);
+        assert_eq!(origin, Point2::new(1_000_000, -40));
+    }
+}